@@ -3,20 +3,20 @@
 // Полезен для работы с несколькими контекстами одновременно.
 // Пример: применение функций к Option значениям.
 
-/// Трейт для аппликативного функтора.
-pub trait Applicative<T> {
+/// Трейт для аппликативного функтора. 'f - время жизни функции, как в Functor.
+pub trait Applicative<'f, T> {
     type Output<U>;
     fn pure(value: T) -> Self::Output<T>;
     fn apply<U, F>(self, f: Self::Output<F>) -> Self::Output<U>
     where
-        F: Fn(T) -> U;
+        F: Fn(T) -> U + 'f;
 }
 
 // Импортируем Functor для использования в Applicative
-use super::functor::Functor;
+use super::functor::{Functor, Maybe};
 
 /// Реализация Applicative для Option.
-impl<T> Applicative<T> for Option<T> {
+impl<'f, T> Applicative<'f, T> for Option<T> {
     type Output<U> = Option<U>;
 
     fn pure(value: T) -> Option<T> {
@@ -25,7 +25,7 @@ impl<T> Applicative<T> for Option<T> {
 
     fn apply<U, F>(self, f: Option<F>) -> Option<U>
     where
-        F: Fn(T) -> U,
+        F: Fn(T) -> U + 'f,
     {
         match (self, f) {
             (Some(value), Some(func)) => Some(func(value)),
@@ -34,8 +34,45 @@ impl<T> Applicative<T> for Option<T> {
     }
 }
 
+/// Реализация Applicative для Vec: каждая функция применяется к каждому значению.
+impl<'f, T: Clone> Applicative<'f, T> for Vec<T> {
+    type Output<U> = Vec<U>;
+
+    fn pure(value: T) -> Vec<T> {
+        vec![value]
+    }
+
+    fn apply<U, F>(self, f: Vec<F>) -> Vec<U>
+    where
+        F: Fn(T) -> U + 'f,
+    {
+        f.iter()
+            .flat_map(|func| self.iter().cloned().map(func))
+            .collect()
+    }
+}
+
+/// Реализация Applicative для Maybe.
+impl<'f, T> Applicative<'f, T> for Maybe<T> {
+    type Output<U> = Maybe<U>;
+
+    fn pure(value: T) -> Maybe<T> {
+        Maybe::some(value)
+    }
+
+    fn apply<U, F>(self, f: Maybe<F>) -> Maybe<U>
+    where
+        F: Fn(T) -> U + 'f,
+    {
+        match (self.into_option(), f.into_option()) {
+            (Some(value), Some(func)) => Maybe::some(func(value)),
+            _ => Maybe::none(),
+        }
+    }
+}
+
 /// Пользовательский аппликативный функтор - Result с ошибкой.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct AppResult<T, E>(Result<T, E>);
 
 impl<T, E> AppResult<T, E> {
@@ -46,14 +83,19 @@ impl<T, E> AppResult<T, E> {
     pub fn err(error: E) -> Self {
         AppResult(Err(error))
     }
+
+    /// Возвращает обернутый Result.
+    pub fn into_result(self) -> Result<T, E> {
+        self.0
+    }
 }
 
-impl<T, E> Functor<T> for AppResult<T, E> {
+impl<'f, T, E> Functor<'f, T> for AppResult<T, E> {
     type Output<U> = AppResult<U, E>;
 
     fn map<U, F>(self, f: F) -> AppResult<U, E>
     where
-        F: Fn(T) -> U,
+        F: Fn(T) -> U + 'f,
     {
         match self.0 {
             Ok(value) => AppResult::ok(f(value)),
@@ -62,7 +104,7 @@ impl<T, E> Functor<T> for AppResult<T, E> {
     }
 }
 
-impl<T, E> Applicative<T> for AppResult<T, E> {
+impl<'f, T, E> Applicative<'f, T> for AppResult<T, E> {
     type Output<U> = AppResult<U, E>;
 
     fn pure(value: T) -> AppResult<T, E> {
@@ -71,7 +113,7 @@ impl<T, E> Applicative<T> for AppResult<T, E> {
 
     fn apply<U, F>(self, f: AppResult<F, E>) -> AppResult<U, E>
     where
-        F: Fn(T) -> U,
+        F: Fn(T) -> U + 'f,
     {
         match (self.0, f.0) {
             (Ok(value), Ok(func)) => AppResult::ok(func(value)),
@@ -106,4 +148,19 @@ fn test_applicative() {
     let err_value: AppResult<i32, &str> = AppResult::err("Error");
     let app_err = err_value.apply(AppResult::ok(|x: i32| x + 5));
     assert_eq!(app_err, AppResult::err("Error"));
-}
\ No newline at end of file
+
+    // Applicative для Vec: все сочетания функций и значений
+    let values = vec![1, 2, 3];
+    let funcs: Vec<Box<dyn Fn(i32) -> i32>> = vec![Box::new(|x| x + 1), Box::new(|x| x * 10)];
+    assert_eq!(values.apply(funcs), vec![2, 3, 4, 10, 20, 30]);
+
+    // Applicative для Maybe
+    assert_eq!(
+        Maybe::some(4).apply(Maybe::some(|x: i32| x * x)),
+        Maybe::some(16)
+    );
+    assert_eq!(
+        Maybe::<i32>::none().apply(Maybe::some(|x: i32| x * x)),
+        Maybe::none()
+    );
+}
@@ -0,0 +1,105 @@
+// Часы для таймаутов и повторных попыток Promise и для TTL мемоизации.
+// Время внедряется через трейт Clock: SystemClock использует реальное время,
+// а ManualClock сдвигается вручную, что делает тесты детерминированными.
+
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Отложенный вызов таймера.
+type Timer = Box<dyn FnOnce() + Send>;
+
+/// Трейт для источника времени.
+pub trait Clock: Send + Sync {
+    /// Текущее время, отсчитываемое от создания часов.
+    fn now(&self) -> Duration;
+
+    /// Вызывает callback, когда часы дойдут до момента deadline.
+    fn call_at(&self, deadline: Duration, callback: Timer);
+}
+
+/// Реальные часы. Каждый таймер ждет в отдельном потоке.
+pub struct SystemClock {
+    start: Instant,
+}
+
+impl SystemClock {
+    pub fn new() -> Self {
+        SystemClock {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    fn call_at(&self, deadline: Duration, callback: Timer) {
+        let delay = deadline.saturating_sub(self.now());
+        thread::spawn(move || {
+            thread::sleep(delay);
+            callback();
+        });
+    }
+}
+
+struct ManualState {
+    now: Duration,
+    timers: Vec<(Duration, Timer)>,
+}
+
+/// Ручные часы: время идет только при вызове advance.
+pub struct ManualClock {
+    state: Mutex<ManualState>,
+}
+
+impl ManualClock {
+    pub fn new() -> Self {
+        ManualClock {
+            state: Mutex::new(ManualState {
+                now: Duration::ZERO,
+                timers: Vec::new(),
+            }),
+        }
+    }
+
+    /// Сдвигает время вперед и по порядку вызывает наступившие таймеры,
+    /// включая те, что были запланированы самими таймерами.
+    pub fn advance(&self, by: Duration) {
+        let target = self.state.lock().unwrap().now + by;
+        loop {
+            let mut state = self.state.lock().unwrap();
+            let due = state
+                .timers
+                .iter()
+                .enumerate()
+                .filter(|(_, (deadline, _))| *deadline <= target)
+                .min_by_key(|(_, (deadline, _))| *deadline)
+                .map(|(index, _)| index);
+            match due {
+                Some(index) => {
+                    let (deadline, callback) = state.timers.remove(index);
+                    state.now = state.now.max(deadline);
+                    drop(state);
+                    callback();
+                }
+                None => {
+                    state.now = target;
+                    return;
+                }
+            }
+        }
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        self.state.lock().unwrap().now
+    }
+
+    fn call_at(&self, deadline: Duration, callback: Timer) {
+        self.state.lock().unwrap().timers.push((deadline, callback));
+    }
+}
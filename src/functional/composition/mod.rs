@@ -0,0 +1,477 @@
+// Функциональный паттерн Composition: объединение функций в цепочку.
+// Позволяет создавать сложные функции из простых путем композиции.
+// Полезен для создания пайплайнов обработки данных.
+// compose соединяет функции справа налево, как в математике: compose(f, g)(x) = f(g(x)).
+// pipe соединяет их слева направо, в порядке выполнения: pipe(f, g)(x) = g(f(x)).
+// Макросы compose! и pipe! принимают любое число функций, а Pipeline собирает
+// цепочку из упакованных этапов во время выполнения.
+// Именованные этапы Pipeline и приемник трасс позволяют увидеть вход, выход и время
+// каждого этапа одного вызова.
+// Композиция Клейсли соединяет этапы, возвращающие Result, Option или Promise:
+// цепочка прерывается на первой ошибке, а ошибки этапов приводятся к общему типу через From.
+// Пример: композиция функций для преобразования данных.
+
+pub mod trace;
+
+use std::any::Any;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::Instant;
+
+use super::promise::Promise;
+use trace::{StageRecord, Trace, TraceSink, NOT_CAPTURED};
+
+/// Функция композиции: f после g (f ∘ g)
+pub fn compose<A, B, C, F, G>(f: F, g: G) -> impl Fn(A) -> C
+where
+    F: Fn(B) -> C,
+    G: Fn(A) -> B,
+{
+    move |x| f(g(x))
+}
+
+/// Функция композиции для трех функций: f ∘ g ∘ h
+pub fn compose3<A, B, C, D, F, G, H>(f: F, g: G, h: H) -> impl Fn(A) -> D
+where
+    F: Fn(C) -> D,
+    G: Fn(B) -> C,
+    H: Fn(A) -> B,
+{
+    move |x| f(g(h(x)))
+}
+
+/// Функция пайплайна (левая композиция): f затем g
+pub fn pipe<A, B, C, F, G>(f: F, g: G) -> impl Fn(A) -> C
+where
+    F: Fn(A) -> B,
+    G: Fn(B) -> C,
+{
+    move |x| g(f(x))
+}
+
+/// Композиция Клейсли для Result слева направо: f, затем g, если f вернула Ok.
+/// Ошибки обоих этапов приводятся к E через From.
+pub fn pipe_result<A, B, C, E, E1, E2, F, G>(f: F, g: G) -> impl Fn(A) -> Result<C, E>
+where
+    F: Fn(A) -> Result<B, E1>,
+    G: Fn(B) -> Result<C, E2>,
+    E: From<E1> + From<E2>,
+{
+    move |x| {
+        let y = f(x)?;
+        Ok(g(y)?)
+    }
+}
+
+/// Композиция Клейсли для Result справа налево: f после g.
+pub fn compose_result<A, B, C, E, E1, E2, F, G>(f: F, g: G) -> impl Fn(A) -> Result<C, E>
+where
+    F: Fn(B) -> Result<C, E2>,
+    G: Fn(A) -> Result<B, E1>,
+    E: From<E1> + From<E2>,
+{
+    pipe_result(g, f)
+}
+
+/// Композиция Клейсли для Option слева направо: f, затем g, если f вернула Some.
+pub fn pipe_option<A, B, C, F, G>(f: F, g: G) -> impl Fn(A) -> Option<C>
+where
+    F: Fn(A) -> Option<B>,
+    G: Fn(B) -> Option<C>,
+{
+    move |x| f(x).and_then(&g)
+}
+
+/// Композиция Клейсли для Option справа налево: f после g.
+pub fn compose_option<A, B, C, F, G>(f: F, g: G) -> impl Fn(A) -> Option<C>
+where
+    F: Fn(B) -> Option<C>,
+    G: Fn(A) -> Option<B>,
+{
+    pipe_option(g, f)
+}
+
+/// Композиция Клейсли для Promise слева направо. Второй этап не блокирует поток:
+/// он запускается как продолжение, когда разрешится Promise первого этапа.
+pub fn pipe_promise<A, B, C, E, E1, E2, F, G>(f: F, g: G) -> impl Fn(A) -> Promise<C, E>
+where
+    B: Clone + Send + 'static,
+    C: Clone + Send + 'static,
+    E: From<E1> + From<E2> + Clone + Send + 'static,
+    E1: Clone + Send + 'static,
+    E2: Clone + Send + 'static,
+    F: Fn(A) -> Promise<B, E1>,
+    G: Fn(B) -> Promise<C, E2> + Send + Sync + 'static,
+{
+    let g = Arc::new(g);
+    move |x| {
+        let g = Arc::clone(&g);
+        f(x).map_err(E::from)
+            .and_then(move |y| g(y).map_err(E::from))
+    }
+}
+
+/// Композиция Клейсли для Promise справа налево: f после g.
+pub fn compose_promise<A, B, C, E, E1, E2, F, G>(f: F, g: G) -> impl Fn(A) -> Promise<C, E>
+where
+    B: Clone + Send + 'static,
+    C: Clone + Send + 'static,
+    E: From<E1> + From<E2> + Clone + Send + 'static,
+    E1: Clone + Send + 'static,
+    E2: Clone + Send + 'static,
+    F: Fn(B) -> Promise<C, E2> + Send + Sync + 'static,
+    G: Fn(A) -> Promise<B, E1>,
+{
+    pipe_promise(g, f)
+}
+
+/// Композиция любого числа функций справа налево: compose!(f, g, h)(x) = f(g(h(x))).
+#[macro_export]
+macro_rules! compose {
+    ($f:expr $(,)?) => {
+        $f
+    };
+    ($f:expr, $($rest:expr),+ $(,)?) => {
+        $crate::functional::composition::compose($f, $crate::compose!($($rest),+))
+    };
+}
+
+/// Пайплайн из любого числа функций слева направо: pipe!(f, g, h)(x) = h(g(f(x))).
+#[macro_export]
+macro_rules! pipe {
+    ($f:expr $(,)?) => {
+        $f
+    };
+    ($f:expr, $($rest:expr),+ $(,)?) => {
+        $crate::functional::composition::pipe($f, $crate::pipe!($($rest),+))
+    };
+}
+
+/// Отладочное представление значения со стертым типом.
+type Describe = Box<dyn Fn(&dyn Any) -> String>;
+
+/// Функция этапа со стертыми типами входа и выхода.
+type ErasedFn = Box<dyn Fn(Box<dyn Any>) -> Box<dyn Any>>;
+
+/// Этап пайплайна.
+struct Stage {
+    name: String,
+    run: ErasedFn,
+    describe: Option<Describe>,
+}
+
+/// Пайплайн из упакованных этапов, который можно наращивать во время выполнения.
+/// Типы соседних этапов могут различаться; согласованность проверяется при сборке:
+/// Pipeline<A, B> принимает A и возвращает B.
+/// С подключенным приемником каждый вызов записывает трассу этапов (см. trace).
+pub struct Pipeline<A, B> {
+    stages: Vec<Stage>,
+    sink: Option<Box<dyn TraceSink>>,
+    describe_input: Option<Describe>,
+    types: PhantomData<fn(A) -> B>,
+}
+
+impl<A: 'static> Pipeline<A, A> {
+    /// Создает пустой пайплайн, возвращающий вход без изменений.
+    pub fn new() -> Self {
+        Pipeline {
+            stages: Vec::new(),
+            sink: None,
+            describe_input: None,
+            types: PhantomData,
+        }
+    }
+}
+
+impl<A: 'static> Default for Pipeline<A, A> {
+    fn default() -> Self {
+        Pipeline::new()
+    }
+}
+
+impl<A: 'static, B: 'static> Pipeline<A, B> {
+    /// Добавляет этап в конец пайплайна.
+    pub fn then<C, F>(self, stage: F) -> Pipeline<A, C>
+    where
+        C: 'static,
+        F: Fn(B) -> C + 'static,
+    {
+        self.then_boxed(Box::new(stage))
+    }
+
+    /// Добавляет упакованный этап в конец пайплайна.
+    pub fn then_boxed<C: 'static>(self, stage: Box<dyn Fn(B) -> C>) -> Pipeline<A, C> {
+        let name = format!("stage {}", self.stages.len() + 1);
+        self.push_stage(name, stage, None)
+    }
+
+    /// Добавляет именованный этап, выход которого попадает в трассу.
+    pub fn then_named<C, F>(self, name: &str, stage: F) -> Pipeline<A, C>
+    where
+        C: Debug + 'static,
+        F: Fn(B) -> C + 'static,
+    {
+        self.push_stage(name.to_string(), Box::new(stage), Some(describe::<C>()))
+    }
+
+    fn push_stage<C: 'static>(
+        mut self,
+        name: String,
+        stage: Box<dyn Fn(B) -> C>,
+        describe: Option<Describe>,
+    ) -> Pipeline<A, C> {
+        self.stages.push(Stage {
+            name,
+            run: Box::new(move |input: Box<dyn Any>| {
+                let input = input
+                    .downcast::<B>()
+                    .expect("stage input type is checked when the pipeline is built");
+                Box::new(stage(*input)) as Box<dyn Any>
+            }),
+            describe,
+        });
+        self.retype()
+    }
+
+    /// Добавляет все этапы другого пайплайна после этапов текущего.
+    /// Приемник трасс другого пайплайна не переносится.
+    pub fn append<C: 'static>(mut self, other: Pipeline<B, C>) -> Pipeline<A, C> {
+        self.stages.extend(other.stages);
+        self.retype()
+    }
+
+    /// Композиция справа налево: сначала выполняется inner, затем текущий пайплайн.
+    /// Сохраняется приемник трасс inner.
+    pub fn compose<Z: 'static>(self, inner: Pipeline<Z, A>) -> Pipeline<Z, B> {
+        inner.append(self)
+    }
+
+    /// Число этапов.
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// Прогоняет значение через все этапы по порядку.
+    pub fn run(&self, input: A) -> B {
+        let input = Box::new(input) as Box<dyn Any>;
+        let output = match &self.sink {
+            Some(sink) => self.run_traced(input, sink.as_ref()),
+            None => self
+                .stages
+                .iter()
+                .fold(input, |value, stage| (stage.run)(value)),
+        };
+        *output
+            .downcast::<B>()
+            .expect("pipeline output type is checked when the pipeline is built")
+    }
+
+    fn run_traced(&self, input: Box<dyn Any>, sink: &dyn TraceSink) -> Box<dyn Any> {
+        let started = Instant::now();
+        let mut previous = capture(self.describe_input.as_ref(), input.as_ref());
+        let trace_input = previous.clone();
+        let mut records = Vec::with_capacity(self.stages.len());
+        let mut value = input;
+        for (index, stage) in self.stages.iter().enumerate() {
+            let stage_started = Instant::now();
+            value = (stage.run)(value);
+            let elapsed = stage_started.elapsed();
+            let output = capture(stage.describe.as_ref(), value.as_ref());
+            records.push(StageRecord {
+                index,
+                name: stage.name.clone(),
+                input: previous,
+                output: output.clone(),
+                elapsed,
+            });
+            previous = output;
+        }
+        sink.record(&Trace {
+            input: trace_input,
+            stages: records,
+            elapsed: started.elapsed(),
+        });
+        value
+    }
+
+    fn retype<C>(self) -> Pipeline<A, C> {
+        Pipeline {
+            stages: self.stages,
+            sink: self.sink,
+            describe_input: self.describe_input,
+            types: PhantomData,
+        }
+    }
+}
+
+impl<A: Debug + 'static, B: 'static> Pipeline<A, B> {
+    /// Подключает приемник трасс. Вход пайплайна попадает в трассу через Debug.
+    pub fn with_sink<S: TraceSink + 'static>(mut self, sink: S) -> Self {
+        self.sink = Some(Box::new(sink));
+        self.describe_input = Some(describe::<A>());
+        self
+    }
+}
+
+fn describe<T: Debug + 'static>() -> Describe {
+    Box::new(|value: &dyn Any| match value.downcast_ref::<T>() {
+        Some(value) => format!("{:?}", value),
+        None => NOT_CAPTURED.to_string(),
+    })
+}
+
+fn capture(describe: Option<&Describe>, value: &dyn Any) -> String {
+    match describe {
+        Some(describe) => describe(value),
+        None => NOT_CAPTURED.to_string(),
+    }
+}
+
+#[test]
+fn test_composition() {
+    // Простые функции
+    let add_one = |x: i32| x + 1;
+    let multiply_two = |x: i32| x * 2;
+    let to_string = |x: i32| format!("Result: {}", x);
+
+    // Композиция: add_one ∘ multiply_two
+    let composed = compose(add_one, multiply_two);
+    assert_eq!(composed(3), 7); // 3 * 2 = 6, 6 + 1 = 7
+
+    // Композиция трех функций
+    let composed3 = compose3(to_string, multiply_two, add_one);
+    assert_eq!(composed3(3), "Result: 8");
+
+    // Пайплайн: add_one затем multiply_two
+    let piped = pipe(add_one, multiply_two);
+    assert_eq!(piped(3), 8); // 3 + 1 = 4, 4 * 2 = 8
+
+    // Более сложный пример
+    let square = |x: i32| x * x;
+    let negate = |x: i32| -x;
+    let double = |x: i32| x * 2;
+
+    let complex = compose3(double, negate, square);
+    assert_eq!(complex(3), -18); // (3^2) = 9, -9 = -9, -9 * 2 = -18
+
+    // Макросы произвольной длины с разными типами этапов
+    let half = |x: i32| x as f64 / 2.0;
+    let show = |x: f64| format!("{:.1}", x);
+    let described = crate::pipe!(add_one, square, half, show);
+    assert_eq!(described(3), "8.0");
+    let same = crate::compose!(show, half, square, add_one);
+    assert_eq!(same(3), described(3));
+    assert_eq!(crate::compose!(negate)(5), -5);
+
+    // Pipeline, собираемый во время выполнения
+    let mut pipeline = Pipeline::new().then(|s: &str| s.trim().to_string());
+    for _ in 0..2 {
+        pipeline = pipeline.then(|s: String| s + "!");
+    }
+    let pipeline = pipeline.then(|s: String| s.len());
+    assert_eq!(pipeline.len(), 4);
+    assert_eq!(pipeline.run("  hi "), 4);
+
+    // Упакованные этапы и композиция пайплайнов в обе стороны
+    let stages: Vec<Box<dyn Fn(i32) -> i32>> = vec![Box::new(add_one), Box::new(double)];
+    let arithmetic = stages.into_iter().fold(Pipeline::new(), |pipeline, stage| {
+        pipeline.then_boxed(stage)
+    });
+    let render = Pipeline::new().then(to_string);
+    assert_eq!(render.compose(arithmetic).run(4), "Result: 10");
+
+    let parse = Pipeline::new().then(|s: &str| s.parse::<i32>().unwrap());
+    let full = parse.append(Pipeline::new().then(negate));
+    assert_eq!(full.run("12"), -12);
+    let identity = Pipeline::<u8, u8>::new();
+    assert!(identity.is_empty() && !full.is_empty());
+    assert_eq!(identity.run(7), 7);
+}
+
+#[test]
+fn test_kleisli_composition() {
+    use std::num::ParseIntError;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum AppError {
+        Parse(String),
+        Range(String),
+    }
+
+    impl From<ParseIntError> for AppError {
+        fn from(error: ParseIntError) -> Self {
+            AppError::Parse(error.to_string())
+        }
+    }
+
+    impl From<String> for AppError {
+        fn from(message: String) -> Self {
+            AppError::Range(message)
+        }
+    }
+
+    impl From<AppError> for String {
+        fn from(error: AppError) -> Self {
+            format!("{:?}", error)
+        }
+    }
+
+    let parse = |s: &str| s.trim().parse::<u8>();
+    let percent = |x: u8| {
+        if x <= 100 {
+            Ok(x)
+        } else {
+            Err(format!("{} is above 100", x))
+        }
+    };
+
+    // Result: прерывание на первой ошибке и приведение ошибок через From
+    let read_percent = pipe_result(parse, percent);
+    let value: Result<u8, AppError> = read_percent(" 42 ");
+    assert_eq!(value, Ok(42));
+    assert_eq!(
+        read_percent("150"),
+        Err(AppError::Range("150 is above 100".to_string()))
+    );
+    assert!(matches!(read_percent("abc"), Err(AppError::Parse(_))));
+
+    // Справа налево - та же цепочка; этапы можно наращивать
+    let halve = |x: u8| Ok::<_, AppError>(x / 2);
+    let read_half = compose_result(halve, read_percent);
+    let half: Result<u8, AppError> = read_half("64");
+    assert_eq!(half, Ok(32));
+
+    // Option
+    let first_word = |s: &str| s.split_whitespace().next().map(str::to_string);
+    let first_char = |s: String| s.chars().next();
+    assert_eq!(
+        pipe_option(first_word, first_char)("  hello world"),
+        Some('h')
+    );
+    assert_eq!(compose_option(first_char, first_word)("   "), None);
+
+    // Promise: второй этап запускается, когда разрешится первый
+    let (pending, resolver) = Promise::<String, AppError>::pending();
+    let fetch = move |_: u32| pending.clone();
+    let validate = |body: String| {
+        if body.is_empty() {
+            Promise::rejected("empty body".to_string())
+        } else {
+            Promise::fulfilled(body.len())
+        }
+    };
+    let load = pipe_promise(fetch, validate);
+    let loaded: Promise<usize, AppError> = load(1);
+    assert!(loaded.is_pending());
+    resolver.resolve("payload".to_string());
+    assert_eq!(loaded.result(), Some(Ok(7)));
+
+    let empty = |_: u32| Promise::<String, AppError>::fulfilled(String::new());
+    let rejected: Promise<usize, String> = compose_promise(validate, empty)(0);
+    assert_eq!(rejected.result(), Some(Err("empty body".to_string())));
+}
@@ -0,0 +1,170 @@
+// Трассировка пайплайнов.
+// Пайплайн с подключенным приемником (TraceSink) записывает для каждого вызова
+// структурированную трассу: имя, вход, выход и время каждого этапа. По трассе видно,
+// какой этап изменил значение, без отладочной печати внутри этапов.
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Выход этапа, для которого не задано отладочное представление.
+pub const NOT_CAPTURED: &str = "<not captured>";
+
+/// Запись об одном этапе.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StageRecord {
+    /// Номер этапа, начиная с нуля.
+    pub index: usize,
+    pub name: String,
+    pub input: String,
+    pub output: String,
+    pub elapsed: Duration,
+}
+
+/// Трасса одного вызова пайплайна.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trace {
+    pub input: String,
+    pub stages: Vec<StageRecord>,
+    pub elapsed: Duration,
+}
+
+impl Trace {
+    /// Итоговый выход пайплайна.
+    pub fn output(&self) -> &str {
+        self.stages
+            .last()
+            .map_or(self.input.as_str(), |stage| stage.output.as_str())
+    }
+
+    /// Запись этапа по имени.
+    pub fn stage(&self, name: &str) -> Option<&StageRecord> {
+        self.stages.iter().find(|stage| stage.name == name)
+    }
+
+    /// Первый этап, выход которого удовлетворяет условию.
+    pub fn first_stage_where<P>(&self, predicate: P) -> Option<&StageRecord>
+    where
+        P: Fn(&StageRecord) -> bool,
+    {
+        self.stages.iter().find(|stage| predicate(stage))
+    }
+}
+
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "input: {}", self.input)?;
+        for stage in &self.stages {
+            writeln!(
+                f,
+                "{:>3}. {}: {} -> {} ({:?})",
+                stage.index + 1,
+                stage.name,
+                stage.input,
+                stage.output,
+                stage.elapsed
+            )?;
+        }
+        write!(f, "total: {:?}", self.elapsed)
+    }
+}
+
+/// Трейт для приемника трасс.
+pub trait TraceSink {
+    fn record(&self, trace: &Trace);
+}
+
+/// Разделяемый приемник: трассы можно читать, пока пайплайн им пользуется.
+impl<S: TraceSink> TraceSink for Arc<S> {
+    fn record(&self, trace: &Trace) {
+        self.as_ref().record(trace);
+    }
+}
+
+/// Приемник, сохраняющий трассы в памяти.
+#[derive(Debug, Default)]
+pub struct MemorySink {
+    traces: Mutex<Vec<Trace>>,
+}
+
+impl MemorySink {
+    pub fn new() -> Self {
+        MemorySink::default()
+    }
+
+    /// Все записанные трассы в порядке вызовов.
+    pub fn traces(&self) -> Vec<Trace> {
+        self.traces.lock().unwrap().clone()
+    }
+
+    /// Трасса последнего вызова.
+    pub fn last(&self) -> Option<Trace> {
+        self.traces.lock().unwrap().last().cloned()
+    }
+}
+
+impl TraceSink for MemorySink {
+    fn record(&self, trace: &Trace) {
+        self.traces.lock().unwrap().push(trace.clone());
+    }
+}
+
+#[test]
+fn test_pipeline_trace() {
+    use super::Pipeline;
+
+    // Двенадцать этапов, один из которых портит значение
+    let mut pipeline = Pipeline::new();
+    for step in 1..=12 {
+        let name = format!("add {}", step);
+        pipeline = if step == 7 {
+            pipeline.then_named(&name, move |x: i64| x - step)
+        } else {
+            pipeline.then_named(&name, move |x: i64| x + step)
+        };
+    }
+    let sink = Arc::new(MemorySink::new());
+    let pipeline = pipeline.with_sink(Arc::clone(&sink));
+
+    assert_eq!(pipeline.run(0), 64);
+    let trace = sink.last().unwrap();
+    assert_eq!(trace.input, "0");
+    assert_eq!(trace.output(), "64");
+    assert_eq!(trace.stages.len(), 12);
+
+    // Виновный этап - тот, чей выход меньше входа
+    let culprit = trace
+        .first_stage_where(|stage| {
+            stage.output.parse::<i64>().unwrap() < stage.input.parse::<i64>().unwrap()
+        })
+        .unwrap();
+    assert_eq!(culprit.name, "add 7");
+    assert_eq!(
+        (culprit.input.as_str(), culprit.output.as_str()),
+        ("21", "14")
+    );
+    assert_eq!(trace.stage("add 8").unwrap().input, "14");
+
+    // Каждый вызов дает отдельную трассу
+    pipeline.run(100);
+    assert_eq!(sink.traces().len(), 2);
+    assert_eq!(sink.last().unwrap().input, "100");
+
+    // Безымянные этапы получают номер, их выход не захватывается
+    let sink = Arc::new(MemorySink::new());
+    let mixed = Pipeline::new()
+        .then(|s: &str| s.len())
+        .then_named("double", |n: usize| n * 2)
+        .with_sink(Arc::clone(&sink));
+    assert_eq!(mixed.run("abc"), 6);
+    let trace = sink.last().unwrap();
+    assert_eq!(trace.stages[0].name, "stage 1");
+    assert_eq!(trace.stages[0].output, NOT_CAPTURED);
+    assert_eq!(trace.stages[1].input, NOT_CAPTURED);
+    assert_eq!(trace.output(), "6");
+    assert!(trace.to_string().contains("2. double: <not captured> -> 6"));
+
+    // Без приемника трассы не пишутся
+    let plain = Pipeline::new().then_named("inc", |x: i32| x + 1);
+    assert_eq!(plain.run(1), 2);
+}
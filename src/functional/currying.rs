@@ -1,8 +1,97 @@
 // Функциональный паттерн Currying: преобразование функции с несколькими аргументами
 // в последовательность функций с одним аргументом.
 // Полезен для частичного применения функций и создания специализированных функций.
+// Макросы работают с любой функцией или замыканием без упаковки в Box:
+// curry! и curry_right! каррируют функцию от 2 до 8 аргументов (слева направо или
+// справа налево), uncurry! делает обратное, partial! фиксирует часть аргументов,
+// а _ отмечает аргументы, которые передаются при вызове: partial!(f, _, 3).
+// Каррированные и частично примененные функции можно вызывать много раз,
+// поэтому функция и зафиксированные аргументы должны быть Clone.
 // Пример: каррированная функция сложения.
 
+/// Каррирует функцию: curry!(f, 3)(a)(b)(c) == f(a, b, c).
+#[macro_export]
+macro_rules! curry {
+    ($f:expr, 2) => { $crate::curry!(@build $f; [a b]; [a b]) };
+    ($f:expr, 3) => { $crate::curry!(@build $f; [a b c]; [a b c]) };
+    ($f:expr, 4) => { $crate::curry!(@build $f; [a b c d]; [a b c d]) };
+    ($f:expr, 5) => { $crate::curry!(@build $f; [a b c d e]; [a b c d e]) };
+    ($f:expr, 6) => { $crate::curry!(@build $f; [a b c d e g]; [a b c d e g]) };
+    ($f:expr, 7) => { $crate::curry!(@build $f; [a b c d e g h]; [a b c d e g h]) };
+    ($f:expr, 8) => { $crate::curry!(@build $f; [a b c d e g h i]; [a b c d e g h i]) };
+    // Параметры замыканий идут в порядке params, функция вызывается в порядке call.
+    (@build $f:expr; [$($params:ident)+]; [$($call:ident)+]) => {{
+        let f = $f;
+        $crate::curry!(@body f; [$($call)+]; []; $($params)+)
+    }};
+    (@body $f:ident; [$($call:ident)+]; [$($bound:ident)*]; $last:ident) => {
+        move |$last| $f($(::std::clone::Clone::clone(&$call)),+)
+    };
+    (@body $f:ident; [$($call:ident)+]; [$($bound:ident)*]; $param:ident $($rest:ident)+) => {
+        move |$param| {
+            // Клоны нужны, чтобы замыкание оставалось Fn и его можно было вызвать снова.
+            let $f = ::std::clone::Clone::clone(&$f);
+            $(let $bound = ::std::clone::Clone::clone(&$bound);)*
+            $crate::curry!(@body $f; [$($call)+]; [$($bound)* $param]; $($rest)+)
+        }
+    };
+}
+
+/// Каррирует функцию справа налево: curry_right!(f, 3)(c)(b)(a) == f(a, b, c).
+#[macro_export]
+macro_rules! curry_right {
+    ($f:expr, 2) => { $crate::curry!(@build $f; [b a]; [a b]) };
+    ($f:expr, 3) => { $crate::curry!(@build $f; [c b a]; [a b c]) };
+    ($f:expr, 4) => { $crate::curry!(@build $f; [d c b a]; [a b c d]) };
+    ($f:expr, 5) => { $crate::curry!(@build $f; [e d c b a]; [a b c d e]) };
+    ($f:expr, 6) => { $crate::curry!(@build $f; [g e d c b a]; [a b c d e g]) };
+    ($f:expr, 7) => { $crate::curry!(@build $f; [h g e d c b a]; [a b c d e g h]) };
+    ($f:expr, 8) => { $crate::curry!(@build $f; [i h g e d c b a]; [a b c d e g h i]) };
+}
+
+/// Превращает каррированную функцию обратно в функцию нескольких аргументов:
+/// uncurry!(f, 3)(a, b, c) == f(a)(b)(c).
+#[macro_export]
+macro_rules! uncurry {
+    ($f:expr, 2) => { $crate::uncurry!(@build $f; a b) };
+    ($f:expr, 3) => { $crate::uncurry!(@build $f; a b c) };
+    ($f:expr, 4) => { $crate::uncurry!(@build $f; a b c d) };
+    ($f:expr, 5) => { $crate::uncurry!(@build $f; a b c d e) };
+    ($f:expr, 6) => { $crate::uncurry!(@build $f; a b c d e g) };
+    ($f:expr, 7) => { $crate::uncurry!(@build $f; a b c d e g h) };
+    ($f:expr, 8) => { $crate::uncurry!(@build $f; a b c d e g h i) };
+    (@build $f:expr; $($args:ident)+) => {{
+        let f = $f;
+        move |$($args),+| f$(($args))+
+    }};
+}
+
+/// Частичное применение: фиксирует аргументы-выражения, а на месте _ оставляет
+/// параметры новой функции. partial!(f, _, 3)(x) == f(x, 3), partial!(f, 1, _)(y) == f(1, y).
+/// Зафиксированные выражения вычисляются один раз, при создании функции.
+#[macro_export]
+macro_rules! partial {
+    ($f:expr, $($args:tt)+) => {
+        $crate::partial!(@munch ($f); []; []; []; [a b c d e g h i]; $($args)+)
+    };
+    (@munch $f:tt; [$($params:ident)*]; [$($call:tt)*]; [$($lets:tt)*];
+        [$slot:ident $($slots:ident)*]; _ $(, $($rest:tt)*)?) => {
+        $crate::partial!(@munch $f; [$($params)* $slot]; [$($call)* $slot,]; [$($lets)*];
+            [$($slots)*]; $($($rest)*)?)
+    };
+    (@munch $f:tt; [$($params:ident)*]; [$($call:tt)*]; [$($lets:tt)*];
+        [$slot:ident $($slots:ident)*]; $arg:expr $(, $($rest:tt)*)?) => {
+        $crate::partial!(@munch $f; [$($params)*];
+            [$($call)* ::std::clone::Clone::clone(&$slot),]; [$($lets)* let $slot = $arg;];
+            [$($slots)*]; $($($rest)*)?)
+    };
+    (@munch $f:tt; [$($params:ident)*]; [$($call:tt)*]; [$($lets:tt)*]; [$($slots:ident)*];) => {{
+        let f = $f;
+        $($lets)*
+        move |$($params),*| f($($call)*)
+    }};
+}
+
 /// Каррированная функция сложения: принимает первый аргумент и возвращает функцию для второго.
 pub fn add(x: i32) -> impl Fn(i32) -> i32 {
     move |y| x + y
@@ -37,4 +126,65 @@ fn test_currying() {
 
     let greet_hi = format_greeting("Hi");
     assert_eq!(greet_hi("Rust"), "Hi Rust");
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_currying_macros() {
+    fn volume(width: u32, height: u32, depth: u32) -> u32 {
+        width * height * depth
+    }
+
+    // curry! для функции и замыкания; промежуточные функции вызываются многократно
+    let curried = crate::curry!(volume, 3);
+    let flat = curried(2);
+    let row = flat(3);
+    assert_eq!(row(4), 24);
+    assert_eq!(row(5), 30);
+    assert_eq!(flat(10)(1), 20);
+
+    let label = crate::curry!(
+        |name: String, index: usize| format!("{}#{}", name, index),
+        2
+    );
+    let item = label("item".to_string());
+    assert_eq!(item(1), "item#1");
+    assert_eq!(item(2), "item#2");
+
+    // Восемь аргументов
+    let sum8 = |a: i32, b: i32, c: i32, d: i32, e: i32, f: i32, g: i32, h: i32| {
+        a + b + c + d + e + f + g + h
+    };
+    assert_eq!(crate::curry!(sum8, 8)(1)(2)(3)(4)(5)(6)(7)(8), 36);
+
+    // Справа налево: первым передается последний аргумент
+    let divide = |a: f64, b: f64| a / b;
+    let halve = crate::curry_right!(divide, 2)(2.0);
+    assert_eq!(halve(9.0), 4.5);
+    let concat = |a: &str, b: &str, c: &str| format!("{}{}{}", a, b, c);
+    assert_eq!(crate::curry_right!(concat, 3)("c")("b")("a"), "abc");
+
+    // uncurry! обратно к функции нескольких аргументов
+    assert_eq!(crate::uncurry!(multiply, 3)(2, 3, 4), 24);
+    assert_eq!(crate::uncurry!(crate::curry!(volume, 3), 3)(1, 2, 3), 6);
+
+    // partial! с заполнителями
+    let subtract = |a: i32, b: i32| a - b;
+    let minus_three = crate::partial!(subtract, _, 3);
+    let from_ten = crate::partial!(subtract, 10, _);
+    assert_eq!(minus_three(10), 7);
+    assert_eq!(from_ten(4), 6);
+    let depth_five = crate::partial!(volume, _, _, 5);
+    assert_eq!(depth_five(2, 3), 30);
+    let middle = crate::partial!(concat, "<", _, ">");
+    assert_eq!(middle("tag"), "<tag>");
+    assert_eq!(crate::partial!(volume, 1, 2, 3)(), 6);
+
+    // Зафиксированное значение вычисляется один раз и клонируется при каждом вызове
+    let greet = crate::partial!(
+        |greeting: String, name: &str| format!("{}, {}!", greeting, name),
+        ["Hel", "lo"].concat(),
+        _
+    );
+    assert_eq!(greet("Ann"), "Hello, Ann!");
+    assert_eq!(greet("Bob"), "Hello, Bob!");
+}
@@ -0,0 +1,377 @@
+// Функциональные паттерны Reader, Writer и State: эффекты в виде значений.
+// Reader<R, A> - вычисление, которому нужно окружение R (конфигурация, фабрики):
+// зависимости передаются один раз при запуске, а не через каждую функцию.
+// Writer<W, A> - значение вместе с журналом W; журналы объединяются через Monoid,
+// поэтому шаги возвращают свои записи вместо печати в консоль.
+// State<S, A> - вычисление, которое читает и обновляет состояние S, передавая его
+// от шага к шагу без изменяемых переменных.
+// В apply сначала выполняется эффект значения, затем эффект функции, как у Validation.
+// Reader и State хранят функции до запуска, поэтому Functor и Applicative они
+// реализуют только для 'static функций.
+// Пример: журнал операций со счетом, внедрение фабрики GUI и счетчик в состоянии.
+
+use std::rc::Rc;
+
+use super::applicative::Applicative;
+use super::functor::Functor;
+use super::monad::Monad;
+use super::semigroup::Monoid;
+
+/// Вычисление, читающее окружение R.
+pub struct Reader<R, A> {
+    run: Rc<dyn Fn(&R) -> A>,
+}
+
+impl<R, A> Clone for Reader<R, A> {
+    fn clone(&self) -> Self {
+        Reader {
+            run: Rc::clone(&self.run),
+        }
+    }
+}
+
+impl<R: 'static, A: 'static> Reader<R, A> {
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(&R) -> A + 'static,
+    {
+        Reader { run: Rc::new(f) }
+    }
+
+    /// Выполняет вычисление в окружении.
+    pub fn run(&self, env: &R) -> A {
+        (self.run)(env)
+    }
+
+    /// Связывает вычисления: следующее выбирается по результату текущего.
+    pub fn and_then<B: 'static, F>(self, f: F) -> Reader<R, B>
+    where
+        F: Fn(A) -> Reader<R, B> + 'static,
+    {
+        Reader::new(move |env| f(self.run(env)).run(env))
+    }
+
+    /// Выполняет вычисление в измененном окружении.
+    pub fn local<F>(self, f: F) -> Self
+    where
+        F: Fn(&R) -> R + 'static,
+    {
+        Reader::new(move |env| self.run(&f(env)))
+    }
+}
+
+impl<R: 'static> Reader<R, R> {
+    /// Вычисление, возвращающее само окружение.
+    pub fn ask() -> Self
+    where
+        R: Clone,
+    {
+        Reader::new(R::clone)
+    }
+}
+
+/// Применяет функцию к результату вычисления.
+impl<R: 'static, A: 'static> Functor<'static, A> for Reader<R, A> {
+    type Output<U> = Reader<R, U>;
+
+    fn map<U, F>(self, f: F) -> Reader<R, U>
+    where
+        F: Fn(A) -> U + 'static,
+    {
+        Reader {
+            run: Rc::new(move |env| f(self.run(env))),
+        }
+    }
+}
+
+/// pure не читает окружение, apply применяет функцию, вычисленную в том же окружении.
+impl<R: 'static, A: Clone + 'static> Applicative<'static, A> for Reader<R, A> {
+    type Output<U> = Reader<R, U>;
+
+    fn pure(value: A) -> Reader<R, A> {
+        Reader::new(move |_| value.clone())
+    }
+
+    fn apply<U, F>(self, f: Reader<R, F>) -> Reader<R, U>
+    where
+        F: Fn(A) -> U + 'static,
+    {
+        Reader {
+            run: Rc::new(move |env| {
+                let value = self.run(env);
+                f.run(env)(value)
+            }),
+        }
+    }
+}
+
+/// Значение вместе с журналом.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Writer<W, A> {
+    value: A,
+    log: W,
+}
+
+impl<W, A> Writer<W, A> {
+    pub fn new(value: A, log: W) -> Self {
+        Writer { value, log }
+    }
+
+    pub fn value(&self) -> &A {
+        &self.value
+    }
+
+    pub fn log(&self) -> &W {
+        &self.log
+    }
+
+    /// Значение и накопленный журнал.
+    pub fn run(self) -> (A, W) {
+        (self.value, self.log)
+    }
+}
+
+impl<W: Monoid> Writer<W, ()> {
+    /// Только запись в журнал.
+    pub fn tell(log: W) -> Self {
+        Writer::new((), log)
+    }
+}
+
+impl<W: Monoid, A> Writer<W, A> {
+    /// Добавляет запись к журналу.
+    pub fn with_log(self, log: W) -> Self {
+        Writer::new(self.value, self.log.combine(log))
+    }
+}
+
+impl<'f, W, A> Functor<'f, A> for Writer<W, A> {
+    type Output<U> = Writer<W, U>;
+
+    fn map<U, F>(self, f: F) -> Writer<W, U>
+    where
+        F: Fn(A) -> U + 'f,
+    {
+        Writer::new(f(self.value), self.log)
+    }
+}
+
+impl<'f, W: Monoid, A> Applicative<'f, A> for Writer<W, A> {
+    type Output<U> = Writer<W, U>;
+
+    fn pure(value: A) -> Writer<W, A> {
+        Writer::new(value, W::empty())
+    }
+
+    fn apply<U, F>(self, f: Writer<W, F>) -> Writer<W, U>
+    where
+        F: Fn(A) -> U + 'f,
+    {
+        Writer::new((f.value)(self.value), self.log.combine(f.log))
+    }
+}
+
+/// Реализация монады для Writer: журнал шага дописывается к журналу предыдущих.
+impl<'f, W: Monoid, A> Monad<'f, A> for Writer<W, A> {
+    fn bind<U, F>(self, f: F) -> Writer<W, U>
+    where
+        F: Fn(A) -> Writer<W, U> + 'f,
+    {
+        let next = f(self.value);
+        Writer::new(next.value, self.log.combine(next.log))
+    }
+}
+
+/// Вычисление с состоянием S.
+pub struct State<S, A> {
+    run: Rc<dyn Fn(S) -> (A, S)>,
+}
+
+impl<S, A> Clone for State<S, A> {
+    fn clone(&self) -> Self {
+        State {
+            run: Rc::clone(&self.run),
+        }
+    }
+}
+
+impl<S: 'static, A: 'static> State<S, A> {
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(S) -> (A, S) + 'static,
+    {
+        State { run: Rc::new(f) }
+    }
+
+    /// Выполняет вычисление: результат и итоговое состояние.
+    pub fn run(&self, state: S) -> (A, S) {
+        (self.run)(state)
+    }
+
+    /// Только результат.
+    pub fn eval(&self, state: S) -> A {
+        self.run(state).0
+    }
+
+    /// Только итоговое состояние.
+    pub fn exec(&self, state: S) -> S {
+        self.run(state).1
+    }
+
+    /// Связывает вычисления: следующее получает результат и состояние текущего.
+    pub fn and_then<B: 'static, F>(self, f: F) -> State<S, B>
+    where
+        F: Fn(A) -> State<S, B> + 'static,
+    {
+        State::new(move |state| {
+            let (value, state) = self.run(state);
+            f(value).run(state)
+        })
+    }
+}
+
+impl<S: Clone + 'static> State<S, S> {
+    /// Вычисление, возвращающее текущее состояние.
+    pub fn get() -> Self {
+        State::new(|state: S| (state.clone(), state))
+    }
+}
+
+impl<S: 'static> State<S, ()> {
+    /// Заменяет состояние.
+    pub fn put(state: S) -> Self
+    where
+        S: Clone,
+    {
+        State::new(move |_| ((), state.clone()))
+    }
+
+    /// Изменяет состояние функцией.
+    pub fn modify<F>(f: F) -> Self
+    where
+        F: Fn(S) -> S + 'static,
+    {
+        State::new(move |state| ((), f(state)))
+    }
+}
+
+/// Применяет функцию к результату, не меняя состояния.
+impl<S: 'static, A: 'static> Functor<'static, A> for State<S, A> {
+    type Output<U> = State<S, U>;
+
+    fn map<U, F>(self, f: F) -> State<S, U>
+    where
+        F: Fn(A) -> U + 'static,
+    {
+        State {
+            run: Rc::new(move |state| {
+                let (value, state) = self.run(state);
+                (f(value), state)
+            }),
+        }
+    }
+}
+
+/// pure не меняет состояния, apply применяет функцию, вычисленную следующим шагом.
+impl<S: 'static, A: Clone + 'static> Applicative<'static, A> for State<S, A> {
+    type Output<U> = State<S, U>;
+
+    fn pure(value: A) -> State<S, A> {
+        State::new(move |state| (value.clone(), state))
+    }
+
+    fn apply<U, F>(self, f: State<S, F>) -> State<S, U>
+    where
+        F: Fn(A) -> U + 'static,
+    {
+        State {
+            run: Rc::new(move |state| {
+                let (value, state) = self.run(state);
+                let (func, state) = f.run(state);
+                (func(value), state)
+            }),
+        }
+    }
+}
+
+#[test]
+fn test_effects() {
+    use crate::gang_of_four::creational::abstract_factory::{GUIFactory, MacFactory, WinFactory};
+
+    // Writer: шаги возвращают записи журнала вместо println!
+    fn deposit(balance: i32, amount: i32) -> Writer<Vec<String>, i32> {
+        Writer::new(balance + amount, vec![format!("deposit {}", amount)])
+    }
+    fn withdraw(balance: i32, amount: i32) -> Writer<Vec<String>, i32> {
+        if amount > balance {
+            Writer::new(balance, vec![format!("withdraw {} rejected", amount)])
+        } else {
+            Writer::new(balance - amount, vec![format!("withdraw {}", amount)])
+        }
+    }
+    let account = crate::mdo! {
+        balance <- deposit(0, 100);
+        balance <- withdraw(balance, 30);
+        balance <- withdraw(balance, 500);
+        Writer::tell(vec!["done".to_string()]).map(move |_| balance)
+    };
+    let (balance, log) = account.run();
+    assert_eq!(balance, 70);
+    assert_eq!(
+        log,
+        [
+            "deposit 100",
+            "withdraw 30",
+            "withdraw 500 rejected",
+            "done"
+        ]
+    );
+    let doubled = deposit(1, 1).apply(Writer::new(|x: i32| x * 2, vec!["double".to_string()]));
+    assert_eq!(
+        doubled.run(),
+        (4, vec!["deposit 1".to_string(), "double".to_string()])
+    );
+    assert_eq!(Writer::<String, _>::pure(5).log(), "");
+    let noted = deposit(10, 5).with_log(vec!["checked".to_string()]);
+    assert_eq!(*noted.value(), 15);
+    assert_eq!(noted.log(), &["deposit 5", "checked"]);
+
+    // Reader: фабрика передается один раз при запуске
+    type Env = Box<dyn GUIFactory>;
+    let button = Reader::new(|factory: &Env| factory.create_button().paint());
+    let checkbox = Reader::new(|factory: &Env| factory.create_checkbox().paint());
+    let window = checkbox.apply(button.map(|b| move |c| format!("{} | {}", b, c)));
+    let windows: Env = Box::new(WinFactory);
+    let mac: Env = Box::new(MacFactory);
+    assert!(window.run(&windows).contains("Windows"));
+    assert!(window.run(&mac).contains("macOS"));
+    assert!(!window.run(&mac).contains("Windows"));
+
+    let port = Reader::new(|config: &(String, u16)| config.1);
+    let url = Reader::ask().and_then(move |config: (String, u16)| {
+        port.clone().map(move |p| format!("{}:{}", config.0, p))
+    });
+    let config = ("localhost".to_string(), 8080);
+    assert_eq!(url.run(&config), "localhost:8080");
+    let staging = url.local(|config| (format!("staging.{}", config.0), config.1));
+    assert_eq!(staging.run(&config), "staging.localhost:8080");
+    assert_eq!(Reader::<i32, _>::pure("const").run(&1), "const");
+
+    // State: счетчик без изменяемых переменных
+    fn next_id(prefix: &'static str) -> State<u32, String> {
+        State::new(move |counter| (format!("{}-{}", prefix, counter), counter + 1))
+    }
+    let ids = next_id("user")
+        .and_then(|first| next_id("order").map(move |second| (first.clone(), second)));
+    assert_eq!(
+        ids.run(1),
+        (("user-1".to_string(), "order-2".to_string()), 3)
+    );
+    let reset = State::put(10)
+        .and_then(|_| State::modify(|n: u32| n * 2))
+        .and_then(|_| State::get());
+    assert_eq!(reset.run(0), (20, 20));
+    let sum = next_id("a").apply(next_id("b").map(|b| move |a: String| format!("{}{}", a, b)));
+    assert_eq!(sum.eval(0), "a-0b-1");
+    assert_eq!(State::<u32, _>::pure('x').exec(7), 7);
+}
@@ -0,0 +1,374 @@
+// Функциональные паттерны Foldable и Traversable: обход контейнера целиком.
+// Foldable сворачивает все элементы в одно значение: слева направо, справа налево
+// или через моноид (fold_map), поэтому код агрегации пишется один раз для любого контейнера.
+// Traversable применяет функцию с эффектом (Option, Result) к каждому элементу
+// и сохраняет форму контейнера, если все вызовы успешны.
+// Пример: сумма, максимум и список элементов для Vec, Option и дерева файлов, как в Composite.
+
+use super::functor::{Functor, Maybe};
+use super::semigroup::{Monoid, NonEmpty};
+
+/// Трейт для сворачиваемого контейнера.
+pub trait Foldable<T>: Sized {
+    /// Сворачивает элементы слева направо.
+    fn fold_left<B, F>(self, init: B, f: F) -> B
+    where
+        F: FnMut(B, T) -> B;
+
+    /// Сворачивает элементы справа налево.
+    fn fold_right<B, F>(self, init: B, f: F) -> B
+    where
+        F: FnMut(T, B) -> B;
+
+    /// Отображает каждый элемент в моноид и объединяет результаты.
+    fn fold_map<M, F>(self, mut f: F) -> M
+    where
+        M: Monoid,
+        F: FnMut(T) -> M,
+    {
+        self.fold_left(M::empty(), |acc, item| acc.combine(f(item)))
+    }
+
+    /// Собирает элементы в вектор в порядке обхода.
+    fn to_vec(self) -> Vec<T> {
+        self.fold_left(Vec::new(), |mut items, item| {
+            items.push(item);
+            items
+        })
+    }
+}
+
+impl<T> Foldable<T> for Vec<T> {
+    fn fold_left<B, F>(self, init: B, f: F) -> B
+    where
+        F: FnMut(B, T) -> B,
+    {
+        self.into_iter().fold(init, f)
+    }
+
+    fn fold_right<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(T, B) -> B,
+    {
+        self.into_iter().rev().fold(init, |acc, item| f(item, acc))
+    }
+}
+
+impl<T> Foldable<T> for Option<T> {
+    fn fold_left<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, T) -> B,
+    {
+        match self {
+            Some(value) => f(init, value),
+            None => init,
+        }
+    }
+
+    fn fold_right<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(T, B) -> B,
+    {
+        match self {
+            Some(value) => f(value, init),
+            None => init,
+        }
+    }
+}
+
+impl<T> Foldable<T> for Maybe<T> {
+    fn fold_left<B, F>(self, init: B, f: F) -> B
+    where
+        F: FnMut(B, T) -> B,
+    {
+        self.into_option().fold_left(init, f)
+    }
+
+    fn fold_right<B, F>(self, init: B, f: F) -> B
+    where
+        F: FnMut(T, B) -> B,
+    {
+        self.into_option().fold_right(init, f)
+    }
+}
+
+impl<T> Foldable<T> for NonEmpty<T> {
+    fn fold_left<B, F>(self, init: B, f: F) -> B
+    where
+        F: FnMut(B, T) -> B,
+    {
+        self.into_vec().fold_left(init, f)
+    }
+
+    fn fold_right<B, F>(self, init: B, f: F) -> B
+    where
+        F: FnMut(T, B) -> B,
+    {
+        self.into_vec().fold_right(init, f)
+    }
+}
+
+/// Трейт для контейнера, который можно обойти с эффектом, сохранив его форму.
+/// Обход останавливается на первом None или первой ошибке.
+pub trait Traversable<T>: Functor<'static, T> {
+    fn traverse_option<U, F>(self, f: F) -> Option<Self::Output<U>>
+    where
+        F: FnMut(T) -> Option<U>;
+
+    fn traverse_result<U, E, F>(self, f: F) -> Result<Self::Output<U>, E>
+    where
+        F: FnMut(T) -> Result<U, E>;
+}
+
+impl<T> Traversable<T> for Vec<T> {
+    fn traverse_option<U, F>(self, f: F) -> Option<Vec<U>>
+    where
+        F: FnMut(T) -> Option<U>,
+    {
+        self.into_iter().map(f).collect()
+    }
+
+    fn traverse_result<U, E, F>(self, f: F) -> Result<Vec<U>, E>
+    where
+        F: FnMut(T) -> Result<U, E>,
+    {
+        self.into_iter().map(f).collect()
+    }
+}
+
+impl<T> Traversable<T> for Option<T> {
+    fn traverse_option<U, F>(self, mut f: F) -> Option<Option<U>>
+    where
+        F: FnMut(T) -> Option<U>,
+    {
+        match self {
+            Some(value) => f(value).map(Some),
+            None => Some(None),
+        }
+    }
+
+    fn traverse_result<U, E, F>(self, mut f: F) -> Result<Option<U>, E>
+    where
+        F: FnMut(T) -> Result<U, E>,
+    {
+        match self {
+            Some(value) => f(value).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+impl<T> Traversable<T> for Maybe<T> {
+    fn traverse_option<U, F>(self, f: F) -> Option<Maybe<U>>
+    where
+        F: FnMut(T) -> Option<U>,
+    {
+        self.into_option()
+            .traverse_option(f)
+            .map(|value| match value {
+                Some(value) => Maybe::some(value),
+                None => Maybe::none(),
+            })
+    }
+
+    fn traverse_result<U, E, F>(self, f: F) -> Result<Maybe<U>, E>
+    where
+        F: FnMut(T) -> Result<U, E>,
+    {
+        self.into_option()
+            .traverse_result(f)
+            .map(|value| match value {
+                Some(value) => Maybe::some(value),
+                None => Maybe::none(),
+            })
+    }
+}
+
+/// Дерево с произвольным числом потомков: та же форма, что у Composite (папка и ее
+/// содержимое), но с данными в узлах. Обход идет в прямом порядке: узел раньше потомков.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tree<T> {
+    pub value: T,
+    pub children: Vec<Tree<T>>,
+}
+
+impl<T> Tree<T> {
+    pub fn leaf(value: T) -> Self {
+        Tree {
+            value,
+            children: Vec::new(),
+        }
+    }
+
+    pub fn node(value: T, children: Vec<Tree<T>>) -> Self {
+        Tree { value, children }
+    }
+}
+
+impl<'f, T> Functor<'f, T> for Tree<T> {
+    type Output<U> = Tree<U>;
+
+    fn map<U, F>(self, f: F) -> Tree<U>
+    where
+        F: Fn(T) -> U + 'f,
+    {
+        fn go<T, U>(tree: Tree<T>, f: &dyn Fn(T) -> U) -> Tree<U> {
+            Tree {
+                value: f(tree.value),
+                children: tree
+                    .children
+                    .into_iter()
+                    .map(|child| go(child, f))
+                    .collect(),
+            }
+        }
+        go(self, &f)
+    }
+}
+
+impl<T> Foldable<T> for Tree<T> {
+    fn fold_left<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, T) -> B,
+    {
+        fn go<T, B>(tree: Tree<T>, acc: B, f: &mut dyn FnMut(B, T) -> B) -> B {
+            let acc = f(acc, tree.value);
+            tree.children
+                .into_iter()
+                .fold(acc, |acc, child| go(child, acc, f))
+        }
+        go(self, init, &mut f)
+    }
+
+    fn fold_right<B, F>(self, init: B, f: F) -> B
+    where
+        F: FnMut(T, B) -> B,
+    {
+        self.to_vec().fold_right(init, f)
+    }
+}
+
+impl<T> Traversable<T> for Tree<T> {
+    fn traverse_option<U, F>(self, mut f: F) -> Option<Tree<U>>
+    where
+        F: FnMut(T) -> Option<U>,
+    {
+        fn go<T, U>(tree: Tree<T>, f: &mut dyn FnMut(T) -> Option<U>) -> Option<Tree<U>> {
+            let value = f(tree.value)?;
+            let children = tree
+                .children
+                .into_iter()
+                .map(|child| go(child, f))
+                .collect::<Option<_>>()?;
+            Some(Tree { value, children })
+        }
+        go(self, &mut f)
+    }
+
+    fn traverse_result<U, E, F>(self, mut f: F) -> Result<Tree<U>, E>
+    where
+        F: FnMut(T) -> Result<U, E>,
+    {
+        fn go<T, U, E>(tree: Tree<T>, f: &mut dyn FnMut(T) -> Result<U, E>) -> Result<Tree<U>, E> {
+            let value = f(tree.value)?;
+            let children = tree
+                .children
+                .into_iter()
+                .map(|child| go(child, f))
+                .collect::<Result<_, _>>()?;
+            Ok(Tree { value, children })
+        }
+        go(self, &mut f)
+    }
+}
+
+#[test]
+fn test_foldable() {
+    use super::semigroup::{Max, Sum};
+
+    // Один и тот же код агрегации для разных контейнеров
+    fn total<C: Foldable<i32>>(container: C) -> i32 {
+        container.fold_map(Sum).0
+    }
+    assert_eq!(total(vec![1, 2, 3]), 6);
+    assert_eq!(total(Some(5)), 5);
+    assert_eq!(total(None), 0);
+    assert_eq!(total(Maybe::some(7)), 7);
+    assert_eq!(total(NonEmpty::from_vec(vec![4, 4]).unwrap()), 8);
+
+    // Порядок свертки
+    let digits = vec![1, 2, 3];
+    assert_eq!(
+        digits
+            .clone()
+            .fold_left(String::new(), |acc, d| format!("{}{}", acc, d)),
+        "123"
+    );
+    assert_eq!(
+        digits
+            .clone()
+            .fold_right(String::new(), |d, acc| format!("{}{}", acc, d)),
+        "321"
+    );
+    assert_eq!(digits.clone().fold_right(0, |d, acc| d - acc), 2);
+
+    // Несколько агрегатов за один проход через пару моноидов
+    let (sum, max) = vec![3, 9, 4].fold_map(|x| (Sum(x), Some(Max(x))));
+    assert_eq!((sum, max), (Sum(16), Some(Max(9))));
+    assert_eq!(Maybe::<i32>::none().to_vec(), Vec::<i32>::new());
+
+    // Traversable сохраняет форму контейнера
+    let parse = |s: &str| s.parse::<i32>().ok();
+    assert_eq!(vec!["1", "2"].traverse_option(parse), Some(vec![1, 2]));
+    assert_eq!(vec!["1", "x"].traverse_option(parse), None);
+    assert_eq!(None.traverse_option(parse), Some(None));
+    assert_eq!(
+        Maybe::some("8").traverse_option(parse),
+        Some(Maybe::some(8))
+    );
+    let checked = vec![4, 0, 2].traverse_result(|x: i32| {
+        if x == 0 {
+            Err("division by zero")
+        } else {
+            Ok(12 / x)
+        }
+    });
+    assert_eq!(checked, Err("division by zero"));
+    assert_eq!(Some(3).traverse_result(|x| Ok::<_, ()>(x * 2)), Ok(Some(6)));
+
+    // Дерево файлов, как в Composite: имя и размер в каждом узле
+    let tree = Tree::node(
+        ("Корневая папка", 0),
+        vec![
+            Tree::leaf(("file1.txt", 10)),
+            Tree::node(
+                ("Подпапка", 0),
+                vec![Tree::leaf(("file2.txt", 20)), Tree::leaf(("file3.txt", 5))],
+            ),
+        ],
+    );
+    let names = tree.clone().map(|(name, _)| name).to_vec();
+    assert_eq!(
+        names,
+        [
+            "Корневая папка",
+            "file1.txt",
+            "Подпапка",
+            "file2.txt",
+            "file3.txt"
+        ]
+    );
+    assert_eq!(tree.clone().fold_map(|(_, size)| Sum(size)), Sum(35));
+    let last = tree
+        .clone()
+        .fold_right(None, |(name, _), last| last.or(Some(name)));
+    assert_eq!(last, Some("file3.txt"));
+    let sizes = tree.clone().map(|(_, size)| size);
+    assert_eq!(
+        tree.clone()
+            .traverse_option(|(name, size)| (!name.is_empty()).then_some(size)),
+        Some(sizes)
+    );
+    let small = tree.traverse_result(|(name, size)| if size > 15 { Err(name) } else { Ok(size) });
+    assert_eq!(small, Err("file2.txt"));
+}
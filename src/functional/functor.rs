@@ -4,20 +4,22 @@
 // Пример: применение функций к Option и Vec.
 
 /// Трейт для функтора.
-pub trait Functor<T> {
+/// 'f - время жизни функции: ленивые функторы (Stream, Reader, State) хранят ее
+/// до вычисления и реализуют трейт только для 'static, остальные - для любого 'f.
+pub trait Functor<'f, T> {
     type Output<U>;
     fn map<U, F>(self, f: F) -> Self::Output<U>
     where
-        F: Fn(T) -> U;
+        F: Fn(T) -> U + 'f;
 }
 
 /// Реализация функтора для Option.
-impl<T> Functor<T> for Option<T> {
+impl<'f, T> Functor<'f, T> for Option<T> {
     type Output<U> = Option<U>;
 
     fn map<U, F>(self, f: F) -> Option<U>
     where
-        F: Fn(T) -> U,
+        F: Fn(T) -> U + 'f,
     {
         match self {
             Some(value) => Some(f(value)),
@@ -27,19 +29,19 @@ impl<T> Functor<T> for Option<T> {
 }
 
 /// Реализация функтора для Vec.
-impl<T> Functor<T> for Vec<T> {
+impl<'f, T> Functor<'f, T> for Vec<T> {
     type Output<U> = Vec<U>;
 
     fn map<U, F>(self, f: F) -> Vec<U>
     where
-        F: Fn(T) -> U,
+        F: Fn(T) -> U + 'f,
     {
         self.into_iter().map(f).collect()
     }
 }
 
 /// Пользовательский функтор - Maybe (простая обертка над Option).
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Maybe<T>(Option<T>);
 
 impl<T> Maybe<T> {
@@ -50,14 +52,19 @@ impl<T> Maybe<T> {
     pub fn none() -> Self {
         Maybe(None)
     }
+
+    /// Возвращает обернутый Option.
+    pub fn into_option(self) -> Option<T> {
+        self.0
+    }
 }
 
-impl<T> Functor<T> for Maybe<T> {
+impl<'f, T> Functor<'f, T> for Maybe<T> {
     type Output<U> = Maybe<U>;
 
     fn map<U, F>(self, f: F) -> Maybe<U>
     where
-        F: Fn(T) -> U,
+        F: Fn(T) -> U + 'f,
     {
         match self.0 {
             Some(value) => Maybe::some(f(value)),
@@ -90,4 +97,4 @@ fn test_functor() {
     let maybe_none = Maybe::<i32>::none();
     let mapped_maybe_none = maybe_none.map(|x| x + 8);
     assert_eq!(mapped_maybe_none, Maybe::none());
-}
\ No newline at end of file
+}
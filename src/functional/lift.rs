@@ -0,0 +1,241 @@
+// Операции над несколькими значениями в контексте.
+// Applicative::apply применяет одну обернутую функцию к одному значению; на практике
+// чаще нужно объединить несколько обернутых входов: zip собирает пару, lift2 и lift3
+// поднимают обычную функцию нескольких аргументов в контекст.
+// sequence переворачивает вектор значений в контексте (Vec<Option<T>>) в контекст
+// с вектором (Option<Vec<T>>), traverse делает то же после применения функции.
+
+use super::applicative::{AppResult, Applicative};
+use super::functor::{Functor, Maybe};
+use super::semigroup::Semigroup;
+use super::validation::Validation;
+
+/// Трейт для аппликативного функтора, умеющего объединять два значения в пару.
+pub trait Zip<T>: Applicative<'static, T> + Sized {
+    fn zip<U: Clone>(self, other: Self::Output<U>) -> Self::Output<(T, U)>;
+}
+
+impl<T> Zip<T> for Option<T> {
+    fn zip<U: Clone>(self, other: Option<U>) -> Option<(T, U)> {
+        Option::zip(self, other)
+    }
+}
+
+/// Для Vec - все сочетания значений, как и в Applicative.
+impl<T: Clone> Zip<T> for Vec<T> {
+    fn zip<U: Clone>(self, other: Vec<U>) -> Vec<(T, U)> {
+        self.into_iter()
+            .flat_map(|left| other.iter().map(move |right| (left.clone(), right.clone())))
+            .collect()
+    }
+}
+
+impl<T> Zip<T> for Maybe<T> {
+    fn zip<U: Clone>(self, other: Maybe<U>) -> Maybe<(T, U)> {
+        match self.into_option().zip(other.into_option()) {
+            Some(pair) => Maybe::some(pair),
+            None => Maybe::none(),
+        }
+    }
+}
+
+impl<T, E> Zip<T> for AppResult<T, E> {
+    fn zip<U: Clone>(self, other: AppResult<U, E>) -> AppResult<(T, U), E> {
+        match (self.into_result(), other.into_result()) {
+            (Ok(left), Ok(right)) => AppResult::ok((left, right)),
+            (Err(e), _) | (_, Err(e)) => AppResult::err(e),
+        }
+    }
+}
+
+impl<T, E: Semigroup> Zip<T> for Validation<T, E> {
+    fn zip<U: Clone>(self, other: Validation<U, E>) -> Validation<(T, U), E> {
+        Validation::zip(self, other)
+    }
+}
+
+/// Поднимает функцию двух аргументов в контекст.
+pub fn lift2<'f, A, T, U, R, F>(f: F, a: A, b: A::Output<U>) -> A::Output<R>
+where
+    A: Zip<T>,
+    U: Clone,
+    F: Fn(T, U) -> R + 'f,
+    A::Output<(T, U)>: Functor<'f, (T, U), Output<R> = A::Output<R>>,
+{
+    a.zip(b).map(move |(t, u)| f(t, u))
+}
+
+/// Поднимает функцию трех аргументов в контекст.
+pub fn lift3<'f, A, T, U, V, R, F>(f: F, a: A, b: A::Output<U>, c: A::Output<V>) -> A::Output<R>
+where
+    A: Zip<T>,
+    U: Clone,
+    V: Clone,
+    F: Fn(T, U, V) -> R + 'f,
+    A::Output<(T, U)>: Zip<(T, U), Output<V> = A::Output<V>>,
+    <A::Output<(T, U)> as Applicative<'static, (T, U)>>::Output<((T, U), V)>:
+        Functor<'f, ((T, U), V), Output<R> = A::Output<R>>,
+{
+    a.zip(b).zip(c).map(move |((t, u), v)| f(t, u, v))
+}
+
+/// Трейт для перестановки контекстов: вектор значений в контексте -> контекст с вектором.
+pub trait Sequence {
+    type Output;
+    fn sequence(self) -> Self::Output;
+}
+
+/// None, если хотя бы одно значение отсутствует.
+impl<T> Sequence for Vec<Option<T>> {
+    type Output = Option<Vec<T>>;
+
+    fn sequence(self) -> Option<Vec<T>> {
+        self.into_iter().collect()
+    }
+}
+
+impl<T> Sequence for Vec<Maybe<T>> {
+    type Output = Maybe<Vec<T>>;
+
+    fn sequence(self) -> Maybe<Vec<T>> {
+        match self.into_iter().map(Maybe::into_option).collect() {
+            Some(values) => Maybe::some(values),
+            None => Maybe::none(),
+        }
+    }
+}
+
+/// Первая ошибка в порядке элементов.
+impl<T, E> Sequence for Vec<AppResult<T, E>> {
+    type Output = AppResult<Vec<T>, E>;
+
+    fn sequence(self) -> AppResult<Vec<T>, E> {
+        match self.into_iter().map(AppResult::into_result).collect() {
+            Ok(values) => AppResult::ok(values),
+            Err(e) => AppResult::err(e),
+        }
+    }
+}
+
+/// Ошибки всех элементов накапливаются.
+impl<T, E: Semigroup> Sequence for Vec<Validation<T, E>> {
+    type Output = Validation<Vec<T>, E>;
+
+    fn sequence(self) -> Validation<Vec<T>, E> {
+        let mut result = Validation::valid(Vec::new());
+        for item in self {
+            result = match result.zip(item) {
+                Validation::Valid((mut values, value)) => {
+                    values.push(value);
+                    Validation::Valid(values)
+                }
+                Validation::Invalid(error) => Validation::Invalid(error),
+            };
+        }
+        result
+    }
+}
+
+/// Применяет функцию к каждому значению и переставляет контексты.
+pub fn traverse<T, M, F>(values: Vec<T>, f: F) -> <Vec<M> as Sequence>::Output
+where
+    F: FnMut(T) -> M,
+    Vec<M>: Sequence,
+{
+    values.into_iter().map(f).collect::<Vec<M>>().sequence()
+}
+
+#[test]
+fn test_lift() {
+    use super::semigroup::NonEmpty;
+
+    // zip и lift2/lift3 над Option
+    assert_eq!(Zip::zip(Some(1), Some("one")), Some((1, "one")));
+    assert_eq!(lift2(|a: i32, b: i32| a + b, Some(1), Some(2)), Some(3));
+    assert_eq!(lift2(|a: i32, b: i32| a + b, Some(1), None), None);
+    assert_eq!(
+        lift3(
+            |a: i32, b: i32, c: i32| a * b * c,
+            Some(2),
+            Some(3),
+            Some(4)
+        ),
+        Some(24)
+    );
+
+    // Над Vec - все сочетания
+    assert_eq!(
+        lift2(
+            |a: i32, b: &str| format!("{}{}", b, a),
+            vec![1, 2],
+            vec!["x", "y"]
+        ),
+        vec!["x1", "y1", "x2", "y2"]
+    );
+
+    // Над Maybe и AppResult
+    assert_eq!(
+        lift2(|a: i32, b: i32| a - b, Maybe::some(5), Maybe::some(3)),
+        Maybe::some(2)
+    );
+    let total = lift3(
+        |a: i32, b: i32, c: i32| a + b + c,
+        AppResult::<i32, &str>::ok(1),
+        AppResult::err("second"),
+        AppResult::err("third"),
+    );
+    assert_eq!(total, AppResult::err("second"));
+
+    // Над Validation ошибки накапливаются
+    let total = lift3(
+        |a: i32, b: i32, c: i32| a + b + c,
+        Validation::<i32, NonEmpty<&str>>::valid(1),
+        Validation::fail("second"),
+        Validation::fail("third"),
+    );
+    assert_eq!(
+        total.into_result().unwrap_err().into_vec(),
+        vec!["second", "third"]
+    );
+
+    // sequence
+    assert_eq!(vec![Some(1), Some(2)].sequence(), Some(vec![1, 2]));
+    assert_eq!(vec![Some(1), None].sequence(), None);
+    assert_eq!(Vec::<Option<i32>>::new().sequence(), Some(vec![]));
+    assert_eq!(
+        vec![Maybe::some(1), Maybe::none()].sequence(),
+        Maybe::none()
+    );
+    assert_eq!(
+        vec![AppResult::ok(1), AppResult::err("a"), AppResult::err("b")].sequence(),
+        AppResult::err("a")
+    );
+    assert_eq!(
+        vec![
+            Validation::valid(1),
+            Validation::invalid(vec!["a"]),
+            Validation::invalid(vec!["b"])
+        ]
+        .sequence(),
+        Validation::invalid(vec!["a", "b"])
+    );
+
+    // traverse
+    let parse = |s: &str| s.parse::<i32>().ok();
+    assert_eq!(traverse(vec!["1", "2", "3"], parse), Some(vec![1, 2, 3]));
+    assert_eq!(traverse(vec!["1", "x"], parse), None);
+    let checked = traverse(vec![1, -2, 3, -4], |x: i32| {
+        if x > 0 {
+            Validation::valid(x)
+        } else {
+            Validation::invalid(vec![format!("{} is negative", x)])
+        }
+    });
+    assert_eq!(
+        checked,
+        Validation::invalid(vec![
+            "-2 is negative".to_string(),
+            "-4 is negative".to_string()
+        ])
+    );
+}
@@ -0,0 +1,217 @@
+// Хуки инструментирования для мемоизации.
+// Кеш сообщает о попаданиях, промахах, вставках и вытеснениях через трейт MemoHooks
+// вместо прямого вывода в консоль. По умолчанию используется NoopHooks,
+// CountingHooks собирает счетчики и гистограмму времени вычислений при промахах.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Трейт для наблюдателя за кешем. Все методы по умолчанию ничего не делают.
+pub trait MemoHooks<K> {
+    /// Значение найдено в кеше.
+    fn on_hit(&self, _key: &K) {}
+
+    /// Значения не было в кеше, оно вычислено за elapsed.
+    fn on_miss(&self, _key: &K, _elapsed: Duration) {}
+
+    /// Значение добавлено в кеш.
+    fn on_insert(&self, _key: &K) {}
+
+    /// Значение вытеснено политикой или устарело.
+    fn on_evict(&self, _key: &K) {}
+}
+
+/// Хуки, которые ничего не делают.
+#[derive(Debug, Default)]
+pub struct NoopHooks;
+
+impl<K> MemoHooks<K> for NoopHooks {}
+
+/// Разделяемые хуки: счетчики можно читать, пока мемоизация ими пользуется.
+impl<K, H: MemoHooks<K>> MemoHooks<K> for Arc<H> {
+    fn on_hit(&self, key: &K) {
+        self.as_ref().on_hit(key);
+    }
+
+    fn on_miss(&self, key: &K, elapsed: Duration) {
+        self.as_ref().on_miss(key, elapsed);
+    }
+
+    fn on_insert(&self, key: &K) {
+        self.as_ref().on_insert(key);
+    }
+
+    fn on_evict(&self, key: &K) {
+        self.as_ref().on_evict(key);
+    }
+}
+
+/// Число корзин гистограммы: корзина i считает длительности меньше 2^i микросекунд,
+/// последняя - все остальные.
+pub const LATENCY_BUCKETS: usize = 24;
+
+/// Гистограмма длительностей с экспоненциальными корзинами.
+#[derive(Debug, Default)]
+pub struct LatencyHistogram {
+    buckets: [AtomicU64; LATENCY_BUCKETS],
+}
+
+impl LatencyHistogram {
+    pub fn record(&self, elapsed: Duration) {
+        let micros = elapsed.as_micros();
+        let bucket = (0..LATENCY_BUCKETS - 1)
+            .find(|&i| micros < 1u128 << i)
+            .unwrap_or(LATENCY_BUCKETS - 1);
+        self.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Количество записей по корзинам.
+    pub fn counts(&self) -> Vec<u64> {
+        self.buckets
+            .iter()
+            .map(|bucket| bucket.load(Ordering::Relaxed))
+            .collect()
+    }
+
+    pub fn total(&self) -> u64 {
+        self.counts().iter().sum()
+    }
+
+    /// Верхняя граница корзины, в которую попадает доля q записей (0.0..=1.0).
+    /// Для последней корзины граница неизвестна, возвращается None.
+    pub fn quantile_upper_bound(&self, q: f64) -> Option<Duration> {
+        let counts = self.counts();
+        let total: u64 = counts.iter().sum();
+        if total == 0 {
+            return None;
+        }
+        let target = ((total as f64) * q.clamp(0.0, 1.0)).ceil().max(1.0) as u64;
+        let mut seen = 0;
+        for (i, count) in counts.iter().enumerate().take(LATENCY_BUCKETS - 1) {
+            seen += count;
+            if seen >= target {
+                return Some(Duration::from_micros(1 << i));
+            }
+        }
+        None
+    }
+}
+
+/// Хуки со счетчиками событий и гистограммой времени вычислений.
+#[derive(Debug, Default)]
+pub struct CountingHooks {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    inserts: AtomicU64,
+    evictions: AtomicU64,
+    latency: LatencyHistogram,
+}
+
+impl CountingHooks {
+    pub fn new() -> Self {
+        CountingHooks::default()
+    }
+
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    pub fn inserts(&self) -> u64 {
+        self.inserts.load(Ordering::Relaxed)
+    }
+
+    pub fn evictions(&self) -> u64 {
+        self.evictions.load(Ordering::Relaxed)
+    }
+
+    /// Гистограмма времени вычислений при промахах.
+    pub fn latency(&self) -> &LatencyHistogram {
+        &self.latency
+    }
+}
+
+impl<K> MemoHooks<K> for CountingHooks {
+    fn on_hit(&self, _key: &K) {
+        self.hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn on_miss(&self, _key: &K, elapsed: Duration) {
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        self.latency.record(elapsed);
+    }
+
+    fn on_insert(&self, _key: &K) {
+        self.inserts.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn on_evict(&self, _key: &K) {
+        self.evictions.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn test_memoization_hooks() {
+    use super::memoize_with;
+    use super::policy::Lru;
+    use std::sync::Mutex;
+    use std::thread;
+
+    // Счетчики и гистограмма
+    let hooks = Arc::new(CountingHooks::new());
+    let mut slow_double = memoize_with(
+        |x: u32| {
+            if x == 0 {
+                thread::sleep(Duration::from_millis(5));
+            }
+            x * 2
+        },
+        Lru::new(2),
+    )
+    .with_hooks(Arc::clone(&hooks));
+
+    for x in [0, 1, 0, 2, 3] {
+        slow_double.call(x);
+    }
+    assert_eq!(hooks.hits(), 1);
+    assert_eq!(hooks.misses(), 4);
+    assert_eq!(hooks.inserts(), 4);
+    assert_eq!(hooks.evictions(), 2);
+    assert_eq!(hooks.latency().total(), 4);
+    // Медленное вычисление попало в корзину не меньше 4 мс, быстрые - в младшие
+    assert!(hooks.latency().quantile_upper_bound(1.0).unwrap() >= Duration::from_millis(4));
+    assert!(hooks.latency().quantile_upper_bound(0.5).unwrap() < Duration::from_millis(4));
+
+    // С хуками мемоизация по-прежнему Send: ее можно передать в другой поток
+    let worker = thread::spawn(move || slow_double.call(3));
+    assert_eq!(worker.join().unwrap(), 6);
+    assert_eq!(hooks.hits(), 2);
+
+    // Пользовательские хуки видят ключи событий
+    #[derive(Default)]
+    struct Journal(Mutex<Vec<String>>);
+
+    impl MemoHooks<u32> for Journal {
+        fn on_hit(&self, key: &u32) {
+            self.0.lock().unwrap().push(format!("hit {}", key));
+        }
+
+        fn on_evict(&self, key: &u32) {
+            self.0.lock().unwrap().push(format!("evict {}", key));
+        }
+    }
+
+    let journal = Arc::new(Journal::default());
+    let mut square = memoize_with(|x: u32| x * x, Lru::new(1)).with_hooks(Arc::clone(&journal));
+    square.call(1);
+    square.call(1);
+    square.call(2);
+    assert_eq!(*journal.0.lock().unwrap(), vec!["hit 1", "evict 1"]);
+
+    // Гистограмма пустая - квантиля нет
+    assert_eq!(LatencyHistogram::default().quantile_upper_bound(0.5), None);
+}
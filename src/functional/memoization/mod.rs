@@ -0,0 +1,249 @@
+// Функциональный паттерн Memoization: кеширование результатов функций.
+// Позволяет избежать повторных вычислений для одних и тех же аргументов.
+// Полезен для оптимизации рекурсивных функций или дорогих вычислений.
+// Размер кеша ограничивается политикой вытеснения (см. модуль policy),
+// а события кеша можно наблюдать через хуки (см. модуль hooks).
+// Пример: мемоизация функции Фибоначчи.
+
+pub mod hooks;
+pub mod policy;
+pub mod recursive;
+pub mod snapshot;
+pub mod sync;
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+
+use hooks::{MemoHooks, NoopHooks};
+use policy::{EvictionPolicy, Unbounded};
+
+/// Статистика обращений к кешу.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+}
+
+/// Кеш результатов с политикой вытеснения, статистикой и хуками.
+pub(crate) struct Cache<K, V, P> {
+    map: HashMap<K, V>,
+    policy: P,
+    stats: CacheStats,
+    hooks: Box<dyn MemoHooks<K> + Send + Sync>,
+}
+
+impl<K, V, P> Cache<K, V, P>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+    P: EvictionPolicy<K, V>,
+{
+    pub(crate) fn new(policy: P) -> Self {
+        Cache {
+            map: HashMap::new(),
+            policy,
+            stats: CacheStats::default(),
+            hooks: Box::new(NoopHooks),
+        }
+    }
+
+    pub(crate) fn set_hooks(&mut self, hooks: Box<dyn MemoHooks<K> + Send + Sync>) {
+        self.hooks = hooks;
+    }
+
+    /// Ищет значение. Устаревшее значение удаляется и считается промахом.
+    pub(crate) fn get(&mut self, key: &K) -> Option<V> {
+        if let Some(value) = self.map.get(key) {
+            if !self.policy.is_expired(key) {
+                self.policy.on_access(key);
+                self.stats.hits += 1;
+                self.hooks.on_hit(key);
+                return Some(value.clone());
+            }
+            self.map.remove(key);
+            self.policy.on_remove(key);
+            self.stats.evictions += 1;
+            self.hooks.on_evict(key);
+        }
+        self.stats.misses += 1;
+        None
+    }
+
+    /// Сохраняет значение, вычисленное после промаха за время elapsed.
+    pub(crate) fn insert_computed(&mut self, key: K, value: V, elapsed: Duration) {
+        self.hooks.on_miss(&key, elapsed);
+        self.insert(key, value);
+    }
+
+    /// Добавляет значение и вытесняет записи, которые указала политика.
+    pub(crate) fn insert(&mut self, key: K, value: V) {
+        self.policy.on_insert(&key, &value);
+        self.hooks.on_insert(&key);
+        self.map.insert(key, value);
+        while let Some(victim) = self.policy.victim() {
+            if self.map.remove(&victim).is_some() {
+                self.stats.evictions += 1;
+                self.hooks.on_evict(&victim);
+            }
+        }
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.map.iter()
+    }
+
+    pub(crate) fn contains(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub(crate) fn stats(&self) -> CacheStats {
+        self.stats
+    }
+}
+
+/// Структура для мемоизации функции.
+pub struct Memoized<T, U, F, P = Unbounded>
+where
+    T: Eq + Hash + Clone,
+    U: Clone,
+    F: Fn(T) -> U,
+    P: EvictionPolicy<T, U>,
+{
+    func: F,
+    cache: Cache<T, U, P>,
+}
+
+impl<T, U, F> Memoized<T, U, F>
+where
+    T: Eq + Hash + Clone,
+    U: Clone,
+    F: Fn(T) -> U,
+{
+    /// Создает новую мемоизацию для функции с неограниченным кешем.
+    pub fn new(func: F) -> Self {
+        Memoized::with_policy(func, Unbounded)
+    }
+}
+
+impl<T, U, F, P> Memoized<T, U, F, P>
+where
+    T: Eq + Hash + Clone,
+    U: Clone,
+    F: Fn(T) -> U,
+    P: EvictionPolicy<T, U>,
+{
+    /// Создает мемоизацию с заданной политикой вытеснения.
+    pub fn with_policy(func: F, policy: P) -> Self {
+        Memoized {
+            func,
+            cache: Cache::new(policy),
+        }
+    }
+
+    /// Подключает хуки инструментирования вместо NoopHooks.
+    pub fn with_hooks<H>(mut self, hooks: H) -> Self
+    where
+        H: MemoHooks<T> + Send + Sync + 'static,
+    {
+        self.cache.set_hooks(Box::new(hooks));
+        self
+    }
+
+    /// Вызывает функцию с мемоизацией.
+    pub fn call(&mut self, arg: T) -> U {
+        if let Some(result) = self.cache.get(&arg) {
+            result
+        } else {
+            let started = Instant::now();
+            let result = (self.func)(arg.clone());
+            self.cache
+                .insert_computed(arg, result.clone(), started.elapsed());
+            result
+        }
+    }
+
+    /// Возвращает размер кеша.
+    pub fn cache_size(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Проверяет, есть ли результат для аргумента в кеше.
+    pub fn contains(&self, arg: &T) -> bool {
+        self.cache.contains(arg)
+    }
+
+    /// Возвращает статистику попаданий, промахов и вытеснений.
+    pub fn stats(&self) -> CacheStats {
+        self.cache.stats()
+    }
+}
+
+/// Функция для создания мемоизации.
+pub fn memoize<T, U, F>(func: F) -> Memoized<T, U, F>
+where
+    T: Eq + Hash + Clone,
+    U: Clone,
+    F: Fn(T) -> U,
+{
+    Memoized::new(func)
+}
+
+/// Функция для создания мемоизации с политикой вытеснения.
+pub fn memoize_with<T, U, F, P>(func: F, policy: P) -> Memoized<T, U, F, P>
+where
+    T: Eq + Hash + Clone,
+    U: Clone,
+    F: Fn(T) -> U,
+    P: EvictionPolicy<T, U>,
+{
+    Memoized::with_policy(func, policy)
+}
+
+#[test]
+fn test_memoization() {
+    // Функция Фибоначчи без мемоизации (для сравнения)
+    fn fib(n: u64) -> u64 {
+        if n <= 1 {
+            n
+        } else {
+            fib(n - 1) + fib(n - 2)
+        }
+    }
+
+    // Мемоизация Фибоначчи
+    let mut memo_fib = memoize(|n: u64| {
+        if n <= 1 {
+            n
+        } else {
+            // Рекурсивные вызовы здесь не мемоизируются,
+            // для этого есть memoize_rec из модуля recursive
+            fib(n - 1) + fib(n - 2)
+        }
+    });
+
+    // Первый вызов - вычисление
+    let result1 = memo_fib.call(5);
+    assert_eq!(result1, 5);
+
+    // Второй вызов того же аргумента - из кеша
+    let result2 = memo_fib.call(5);
+    assert_eq!(result2, 5);
+
+    // Размер кеша
+    assert_eq!(memo_fib.cache_size(), 1);
+
+    // Другой аргумент
+    let result3 = memo_fib.call(3);
+    assert_eq!(result3, 2);
+    assert_eq!(memo_fib.cache_size(), 2);
+
+    // Статистика
+    let stats = memo_fib.stats();
+    assert_eq!((stats.hits, stats.misses, stats.evictions), (1, 2, 0));
+}
@@ -0,0 +1,458 @@
+// Политики вытеснения для кеша мемоизации.
+// Кеш сообщает политике о вставках, чтениях и удалениях, а политика решает,
+// какие ключи вытеснить, чтобы кеш не рос бесконечно.
+// Реализованы: Unbounded (без ограничений), LRU, LFU, FIFO, TTL и бюджет по байтам.
+
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::mem;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::functional::clock::{Clock, SystemClock};
+
+/// Трейт для политики вытеснения.
+pub trait EvictionPolicy<K, V> {
+    /// Новый ключ добавлен в кеш.
+    fn on_insert(&mut self, key: &K, value: &V);
+
+    /// Ключ найден в кеше.
+    fn on_access(&mut self, key: &K);
+
+    /// Ключ удален из кеша не через victim (например, истек срок жизни).
+    fn on_remove(&mut self, key: &K);
+
+    /// Устарел ли ключ. Устаревший ключ удаляется при обращении.
+    fn is_expired(&self, _key: &K) -> bool {
+        false
+    }
+
+    /// Следующий ключ на вытеснение или None, если кеш в пределах лимита.
+    /// Возвращенный ключ политика уже забыла.
+    fn victim(&mut self) -> Option<K>;
+}
+
+/// Политика без ограничений: кеш только растет.
+#[derive(Debug, Default)]
+pub struct Unbounded;
+
+impl<K, V> EvictionPolicy<K, V> for Unbounded {
+    fn on_insert(&mut self, _key: &K, _value: &V) {}
+
+    fn on_access(&mut self, _key: &K) {}
+
+    fn on_remove(&mut self, _key: &K) {}
+
+    fn victim(&mut self) -> Option<K> {
+        None
+    }
+}
+
+/// Ключи, упорядоченные по моменту последнего обновления.
+struct Ordered<K> {
+    ticks: HashMap<K, u64>,
+    order: BTreeMap<u64, K>,
+    next_tick: u64,
+}
+
+impl<K: Eq + Hash + Clone> Ordered<K> {
+    fn new() -> Self {
+        Ordered {
+            ticks: HashMap::new(),
+            order: BTreeMap::new(),
+            next_tick: 0,
+        }
+    }
+
+    /// Переносит ключ в конец очереди.
+    fn touch(&mut self, key: &K) {
+        self.remove(key);
+        self.ticks.insert(key.clone(), self.next_tick);
+        self.order.insert(self.next_tick, key.clone());
+        self.next_tick += 1;
+    }
+
+    fn remove(&mut self, key: &K) {
+        if let Some(tick) = self.ticks.remove(key) {
+            self.order.remove(&tick);
+        }
+    }
+
+    fn oldest(&self) -> Option<&K> {
+        self.order.values().next()
+    }
+
+    fn pop_oldest(&mut self) -> Option<K> {
+        let (_, key) = self.order.pop_first()?;
+        self.ticks.remove(&key);
+        Some(key)
+    }
+
+    fn len(&self) -> usize {
+        self.ticks.len()
+    }
+}
+
+/// Least Recently Used: вытесняется ключ, к которому дольше всего не обращались.
+pub struct Lru<K> {
+    capacity: usize,
+    keys: Ordered<K>,
+}
+
+impl<K: Eq + Hash + Clone> Lru<K> {
+    pub fn new(capacity: usize) -> Self {
+        Lru {
+            capacity,
+            keys: Ordered::new(),
+        }
+    }
+}
+
+impl<K: Eq + Hash + Clone, V> EvictionPolicy<K, V> for Lru<K> {
+    fn on_insert(&mut self, key: &K, _value: &V) {
+        self.keys.touch(key);
+    }
+
+    fn on_access(&mut self, key: &K) {
+        self.keys.touch(key);
+    }
+
+    fn on_remove(&mut self, key: &K) {
+        self.keys.remove(key);
+    }
+
+    fn victim(&mut self) -> Option<K> {
+        if self.keys.len() > self.capacity {
+            self.keys.pop_oldest()
+        } else {
+            None
+        }
+    }
+}
+
+/// First In First Out: вытесняется ключ, добавленный раньше всех. Чтения порядок не меняют.
+pub struct Fifo<K> {
+    capacity: usize,
+    keys: Ordered<K>,
+}
+
+impl<K: Eq + Hash + Clone> Fifo<K> {
+    pub fn new(capacity: usize) -> Self {
+        Fifo {
+            capacity,
+            keys: Ordered::new(),
+        }
+    }
+}
+
+impl<K: Eq + Hash + Clone, V> EvictionPolicy<K, V> for Fifo<K> {
+    fn on_insert(&mut self, key: &K, _value: &V) {
+        self.keys.touch(key);
+    }
+
+    fn on_access(&mut self, _key: &K) {}
+
+    fn on_remove(&mut self, key: &K) {
+        self.keys.remove(key);
+    }
+
+    fn victim(&mut self) -> Option<K> {
+        if self.keys.len() > self.capacity {
+            self.keys.pop_oldest()
+        } else {
+            None
+        }
+    }
+}
+
+/// Least Frequently Used: вытесняется ключ с наименьшим числом обращений,
+/// среди равных - тот, к которому дольше всего не обращались.
+/// Только что добавленный ключ не вытесняется, иначе новые ключи не задерживались бы в кеше;
+/// при нулевой емкости кеш, как и с другими политиками, ничего не хранит.
+pub struct Lfu<K> {
+    capacity: usize,
+    ranks: HashMap<K, (u64, u64)>,
+    order: BTreeMap<(u64, u64), K>,
+    next_tick: u64,
+    newest: Option<K>,
+}
+
+impl<K: Eq + Hash + Clone> Lfu<K> {
+    pub fn new(capacity: usize) -> Self {
+        Lfu {
+            capacity,
+            ranks: HashMap::new(),
+            order: BTreeMap::new(),
+            next_tick: 0,
+            newest: None,
+        }
+    }
+
+    fn rank(&mut self, key: &K, frequency: u64) {
+        let rank = (frequency, self.next_tick);
+        self.next_tick += 1;
+        if let Some(old) = self.ranks.insert(key.clone(), rank) {
+            self.order.remove(&old);
+        }
+        self.order.insert(rank, key.clone());
+    }
+}
+
+impl<K: Eq + Hash + Clone, V> EvictionPolicy<K, V> for Lfu<K> {
+    fn on_insert(&mut self, key: &K, _value: &V) {
+        self.rank(key, 1);
+        self.newest = Some(key.clone());
+    }
+
+    fn on_access(&mut self, key: &K) {
+        let frequency = self.ranks.get(key).map_or(0, |(frequency, _)| *frequency);
+        self.rank(key, frequency + 1);
+    }
+
+    fn on_remove(&mut self, key: &K) {
+        if let Some(rank) = self.ranks.remove(key) {
+            self.order.remove(&rank);
+        }
+    }
+
+    fn victim(&mut self) -> Option<K> {
+        if self.ranks.len() <= self.capacity {
+            return None;
+        }
+        let rank = self
+            .order
+            .iter()
+            .find(|(_, key)| self.capacity == 0 || self.newest.as_ref() != Some(*key))
+            .map(|(rank, _)| *rank)?;
+        let key = self.order.remove(&rank)?;
+        self.ranks.remove(&key);
+        Some(key)
+    }
+}
+
+/// Time To Live: значение устаревает через ttl после вставки.
+/// Устаревшие ключи удаляются при обращении и при следующих вставках.
+pub struct Ttl<K> {
+    ttl: Duration,
+    clock: Arc<dyn Clock>,
+    deadlines: HashMap<K, Duration>,
+    keys: Ordered<K>,
+}
+
+impl<K: Eq + Hash + Clone> Ttl<K> {
+    pub fn new(ttl: Duration) -> Self {
+        Ttl::with_clock(ttl, Arc::new(SystemClock::new()))
+    }
+
+    /// TTL с внешними часами, например ManualClock в тестах.
+    pub fn with_clock(ttl: Duration, clock: Arc<dyn Clock>) -> Self {
+        Ttl {
+            ttl,
+            clock,
+            deadlines: HashMap::new(),
+            keys: Ordered::new(),
+        }
+    }
+
+    fn expired(&self, key: &K) -> bool {
+        self.deadlines
+            .get(key)
+            .is_some_and(|deadline| *deadline <= self.clock.now())
+    }
+}
+
+impl<K: Eq + Hash + Clone, V> EvictionPolicy<K, V> for Ttl<K> {
+    fn on_insert(&mut self, key: &K, _value: &V) {
+        self.deadlines
+            .insert(key.clone(), self.clock.now() + self.ttl);
+        self.keys.touch(key);
+    }
+
+    fn on_access(&mut self, _key: &K) {}
+
+    fn on_remove(&mut self, key: &K) {
+        self.deadlines.remove(key);
+        self.keys.remove(key);
+    }
+
+    fn is_expired(&self, key: &K) -> bool {
+        self.expired(key)
+    }
+
+    fn victim(&mut self) -> Option<K> {
+        // При постоянном ttl раньше всех устаревает самый старый ключ.
+        let oldest = self.keys.oldest()?;
+        if !self.expired(oldest) {
+            return None;
+        }
+        let key = self.keys.pop_oldest()?;
+        self.deadlines.remove(&key);
+        Some(key)
+    }
+}
+
+/// Функция оценки размера записи в байтах.
+pub type Weigher<K, V> = fn(&K, &V) -> usize;
+
+/// Бюджет по байтам: при превышении вытесняются давно не использованные записи.
+pub struct MaxBytes<K, V> {
+    budget: usize,
+    used: usize,
+    weigher: Weigher<K, V>,
+    sizes: HashMap<K, usize>,
+    keys: Ordered<K>,
+}
+
+impl<K: Eq + Hash + Clone, V> MaxBytes<K, V> {
+    /// Размер записи оценивается как размер ключа и значения на стеке.
+    pub fn new(budget: usize) -> Self {
+        MaxBytes::with_weigher(budget, |_, _| mem::size_of::<K>() + mem::size_of::<V>())
+    }
+
+    pub fn with_weigher(budget: usize, weigher: Weigher<K, V>) -> Self {
+        MaxBytes {
+            budget,
+            used: 0,
+            weigher,
+            sizes: HashMap::new(),
+            keys: Ordered::new(),
+        }
+    }
+
+    /// Текущий занятый объем.
+    pub fn used_bytes(&self) -> usize {
+        self.used
+    }
+}
+
+impl<K: Eq + Hash + Clone, V> EvictionPolicy<K, V> for MaxBytes<K, V> {
+    fn on_insert(&mut self, key: &K, value: &V) {
+        let size = (self.weigher)(key, value);
+        if let Some(old) = self.sizes.insert(key.clone(), size) {
+            self.used -= old;
+        }
+        self.used += size;
+        self.keys.touch(key);
+    }
+
+    fn on_access(&mut self, key: &K) {
+        self.keys.touch(key);
+    }
+
+    fn on_remove(&mut self, key: &K) {
+        if let Some(size) = self.sizes.remove(key) {
+            self.used -= size;
+        }
+        self.keys.remove(key);
+    }
+
+    fn victim(&mut self) -> Option<K> {
+        if self.used <= self.budget {
+            return None;
+        }
+        let key = self.keys.pop_oldest()?;
+        if let Some(size) = self.sizes.remove(&key) {
+            self.used -= size;
+        }
+        Some(key)
+    }
+}
+
+#[test]
+fn test_eviction_policies() {
+    use super::{memoize_with, CacheStats};
+    use crate::functional::clock::ManualClock;
+
+    let square = |x: u32| x * x;
+
+    // LRU: чтение 1 спасает его, вытесняется 2
+    let mut lru = memoize_with(square, Lru::new(2));
+    lru.call(1);
+    lru.call(2);
+    lru.call(1);
+    lru.call(3);
+    assert_eq!(lru.cache_size(), 2);
+    assert!(lru.contains(&1) && lru.contains(&3) && !lru.contains(&2));
+
+    // FIFO: чтения не влияют, вытесняется первый добавленный
+    let mut fifo = memoize_with(square, Fifo::new(2));
+    fifo.call(1);
+    fifo.call(2);
+    fifo.call(1);
+    fifo.call(3);
+    assert!(!fifo.contains(&1) && fifo.contains(&2) && fifo.contains(&3));
+
+    // LFU: вытесняется самый редко используемый
+    let mut lfu = memoize_with(square, Lfu::new(2));
+    lfu.call(1);
+    lfu.call(1);
+    lfu.call(2);
+    lfu.call(2);
+    lfu.call(2);
+    lfu.call(3);
+    assert!(!lfu.contains(&1) && lfu.contains(&2) && lfu.contains(&3));
+
+    // Нулевая емкость: значение вычисляется, но не хранится
+    let mut empty = memoize_with(square, Lfu::new(0));
+    assert_eq!(empty.call(3), 9);
+    assert_eq!(empty.call(3), 9);
+    assert_eq!(empty.cache_size(), 0);
+    assert_eq!(empty.stats().misses, 2);
+
+    // TTL: значение пересчитывается после истечения срока
+    let clock = Arc::new(ManualClock::new());
+    let mut ttl = memoize_with(
+        square,
+        Ttl::with_clock(Duration::from_secs(10), clock.clone()),
+    );
+    ttl.call(4);
+    clock.advance(Duration::from_secs(5));
+    ttl.call(4);
+    clock.advance(Duration::from_secs(5));
+    ttl.call(4);
+    assert_eq!(
+        ttl.stats(),
+        CacheStats {
+            hits: 1,
+            misses: 2,
+            evictions: 1,
+        }
+    );
+
+    // TTL: устаревшие ключи вычищаются при вставке новых
+    clock.advance(Duration::from_secs(10));
+    ttl.call(5);
+    assert_eq!(ttl.cache_size(), 1);
+
+    // TTL по системным часам: за время теста значение не устаревает
+    let mut system = memoize_with(square, Ttl::new(Duration::from_secs(60)));
+    system.call(6);
+    system.call(6);
+    assert_eq!(system.stats().hits, 1);
+
+    // MaxBytes: строки весят по длине, бюджет 10 байт
+    let mut bytes = memoize_with(
+        |n: usize| "x".repeat(n),
+        MaxBytes::with_weigher(10, |_, value: &String| value.len()),
+    );
+    bytes.call(4);
+    bytes.call(5);
+    assert_eq!(bytes.cache_size(), 2);
+    bytes.call(3);
+    assert!(!bytes.contains(&4) && bytes.contains(&5) && bytes.contains(&3));
+    assert_eq!(bytes.stats().evictions, 1);
+
+    // Значение больше бюджета возвращается, но не кешируется
+    assert_eq!(bytes.call(20).len(), 20);
+    assert!(!bytes.contains(&20));
+
+    // MaxBytes по умолчанию: запись весит как ключ и значение, u32 + u32 = 8 байт
+    let mut policy = MaxBytes::<u32, u32>::new(16);
+    EvictionPolicy::on_insert(&mut policy, &1, &1);
+    EvictionPolicy::on_insert(&mut policy, &2, &4);
+    assert_eq!(policy.used_bytes(), 16);
+    assert_eq!(EvictionPolicy::<u32, u32>::victim(&mut policy), None);
+    EvictionPolicy::on_insert(&mut policy, &3, &9);
+    assert_eq!(EvictionPolicy::<u32, u32>::victim(&mut policy), Some(1));
+    assert_eq!(policy.used_bytes(), 16);
+}
@@ -0,0 +1,154 @@
+// Рекурсивная мемоизация через комбинатор неподвижной точки.
+// Мемоизируемая функция получает первым аргументом ссылку на саму себя (recurse),
+// и каждый рекурсивный вызов проходит через кеш. Так Фибоначчи, расстояние
+// редактирования и другие задачи динамического программирования считаются
+// за число вызовов, равное числу различных подзадач.
+
+use std::hash::Hash;
+use std::time::Instant;
+
+use super::hooks::MemoHooks;
+use super::policy::{EvictionPolicy, Unbounded};
+use super::{Cache, CacheStats};
+
+/// Мемоизация рекурсивной функции.
+pub struct MemoizedRec<T, U, F, P = Unbounded>
+where
+    T: Eq + Hash + Clone,
+    U: Clone,
+    F: Fn(&mut dyn FnMut(T) -> U, T) -> U,
+    P: EvictionPolicy<T, U>,
+{
+    func: F,
+    cache: Cache<T, U, P>,
+}
+
+impl<T, U, F, P> MemoizedRec<T, U, F, P>
+where
+    T: Eq + Hash + Clone,
+    U: Clone,
+    F: Fn(&mut dyn FnMut(T) -> U, T) -> U,
+    P: EvictionPolicy<T, U>,
+{
+    /// Создает рекурсивную мемоизацию с заданной политикой вытеснения.
+    pub fn with_policy(func: F, policy: P) -> Self {
+        MemoizedRec {
+            func,
+            cache: Cache::new(policy),
+        }
+    }
+
+    /// Подключает хуки инструментирования.
+    pub fn with_hooks<H>(mut self, hooks: H) -> Self
+    where
+        H: MemoHooks<T> + Send + Sync + 'static,
+    {
+        self.cache.set_hooks(Box::new(hooks));
+        self
+    }
+
+    /// Вызывает функцию с мемоизацией на всех уровнях рекурсии.
+    pub fn call(&mut self, arg: T) -> U {
+        eval(&self.func, &mut self.cache, arg)
+    }
+
+    /// Возвращает размер кеша.
+    pub fn cache_size(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Возвращает статистику попаданий, промахов и вытеснений.
+    pub fn stats(&self) -> CacheStats {
+        self.cache.stats()
+    }
+}
+
+fn eval<T, U, F, P>(func: &F, cache: &mut Cache<T, U, P>, arg: T) -> U
+where
+    T: Eq + Hash + Clone,
+    U: Clone,
+    F: Fn(&mut dyn FnMut(T) -> U, T) -> U,
+    P: EvictionPolicy<T, U>,
+{
+    if let Some(result) = cache.get(&arg) {
+        return result;
+    }
+    // Время вычисления включает время всех вложенных промахов.
+    let started = Instant::now();
+    let result = func(&mut |next| eval(func, cache, next), arg.clone());
+    cache.insert_computed(arg, result.clone(), started.elapsed());
+    result
+}
+
+/// Функция для создания рекурсивной мемоизации.
+pub fn memoize_rec<T, U, F>(func: F) -> MemoizedRec<T, U, F>
+where
+    T: Eq + Hash + Clone,
+    U: Clone,
+    F: Fn(&mut dyn FnMut(T) -> U, T) -> U,
+{
+    MemoizedRec::with_policy(func, Unbounded)
+}
+
+#[test]
+fn test_recursive_memoization() {
+    use super::hooks::CountingHooks;
+    use std::cell::Cell;
+    use std::sync::Arc;
+
+    // Фибоначчи: каждое n вычисляется ровно один раз
+    let calls = Cell::new(0);
+    let hooks = Arc::new(CountingHooks::new());
+    let mut fib = memoize_rec(|recurse, n: u64| {
+        calls.set(calls.get() + 1);
+        if n <= 1 {
+            n
+        } else {
+            recurse(n - 1) + recurse(n - 2)
+        }
+    })
+    .with_hooks(Arc::clone(&hooks));
+    assert_eq!(fib.call(50), 12_586_269_025);
+    assert_eq!(calls.get(), 51);
+    assert_eq!(fib.cache_size(), 51);
+
+    // Повторный вызов не вычисляет ничего
+    assert_eq!(fib.call(30), 832_040);
+    assert_eq!(calls.get(), 51);
+
+    // Промах на каждое n, попадания - повторные рекурсивные вызовы и вызов для 30
+    let stats = fib.stats();
+    assert_eq!((stats.misses, stats.hits, stats.evictions), (51, 49, 0));
+    assert_eq!((hooks.misses(), hooks.hits()), (51, 49));
+
+    // Расстояние редактирования: подзадача (i, j) - префиксы длины i и j
+    let source: Vec<char> = "kitten".chars().collect();
+    let target: Vec<char> = "sitting".chars().collect();
+    let calls = Cell::new(0);
+    let mut distance = memoize_rec(|recurse, (i, j): (usize, usize)| {
+        calls.set(calls.get() + 1);
+        if i == 0 {
+            return j;
+        }
+        if j == 0 {
+            return i;
+        }
+        let substitution = usize::from(source[i - 1] != target[j - 1]);
+        (recurse((i - 1, j)) + 1)
+            .min(recurse((i, j - 1)) + 1)
+            .min(recurse((i - 1, j - 1)) + substitution)
+    });
+    assert_eq!(distance.call((source.len(), target.len())), 3);
+    assert!(calls.get() <= (source.len() + 1) * (target.len() + 1));
+
+    // Таблица ДП: число путей в сетке из левого верхнего угла в правый нижний
+    let mut paths = memoize_rec(|recurse, (row, col): (u32, u32)| -> u64 {
+        if row == 0 || col == 0 {
+            1
+        } else {
+            recurse((row - 1, col)) + recurse((row, col - 1))
+        }
+    });
+    assert_eq!(paths.call((16, 16)), 601_080_390);
+    assert_eq!(paths.cache_size(), 17 * 17 - 1);
+}
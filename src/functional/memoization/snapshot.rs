@@ -0,0 +1,407 @@
+// Снимки кеша мемоизации на диске.
+// Кеш сохраняется в простой версионированный двоичный формат и загружается при старте,
+// чтобы дорогие вычисления не повторялись между запусками.
+//
+// Формат (все числа little-endian):
+//   "MEMO" | версия: u32 | число записей: u64 | CRC32 предыдущих 16 байт: u32
+//   для каждой записи: длина: u32 | ключ и значение (Encode) | CRC32 ключа и значения: u32
+// Поврежденный заголовок или запись обнаруживается по контрольной сумме,
+// данные после последней записи считаются ошибкой.
+
+use std::fmt;
+use std::fs::{self, File};
+use std::hash::Hash;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use super::policy::EvictionPolicy;
+use super::Memoized;
+
+const MAGIC: &[u8; 4] = b"MEMO";
+const VERSION: u32 = 2;
+
+/// Ошибка чтения или записи снимка.
+#[derive(Debug)]
+pub enum SnapshotError {
+    Io(io::Error),
+    /// Файл не является снимком кеша.
+    BadMagic,
+    /// Снимок записан несовместимой версией формата.
+    UnsupportedVersion(u32),
+    /// Контрольная сумма заголовка не совпала.
+    HeaderChecksum,
+    /// Контрольная сумма записи не совпала.
+    Checksum {
+        entry: u64,
+    },
+    /// Данные закончились раньше, чем ожидалось.
+    Truncated,
+    /// После последней записи остались лишние байты.
+    TrailingData,
+    /// Данные записи не удалось разобрать.
+    Invalid(String),
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SnapshotError::Io(error) => write!(f, "I/O error: {}", error),
+            SnapshotError::BadMagic => write!(f, "not a memoization snapshot"),
+            SnapshotError::UnsupportedVersion(version) => {
+                write!(f, "unsupported snapshot version {}", version)
+            }
+            SnapshotError::HeaderChecksum => write!(f, "checksum mismatch in header"),
+            SnapshotError::Checksum { entry } => write!(f, "checksum mismatch in entry {}", entry),
+            SnapshotError::Truncated => write!(f, "snapshot is truncated"),
+            SnapshotError::TrailingData => write!(f, "unexpected data after the last entry"),
+            SnapshotError::Invalid(reason) => write!(f, "invalid entry: {}", reason),
+        }
+    }
+}
+
+impl From<io::Error> for SnapshotError {
+    fn from(error: io::Error) -> Self {
+        if error.kind() == io::ErrorKind::UnexpectedEof {
+            SnapshotError::Truncated
+        } else {
+            SnapshotError::Io(error)
+        }
+    }
+}
+
+/// Трейт для записи ключа или значения в байты.
+pub trait Encode {
+    fn encode(&self, out: &mut Vec<u8>);
+}
+
+/// Трейт для чтения ключа или значения из байтов. Прочитанные байты отрезаются от input.
+pub trait Decode: Sized {
+    fn decode(input: &mut &[u8]) -> Result<Self, SnapshotError>;
+}
+
+fn take<'a>(input: &mut &'a [u8], len: usize) -> Result<&'a [u8], SnapshotError> {
+    if input.len() < len {
+        return Err(SnapshotError::Truncated);
+    }
+    let (head, tail) = input.split_at(len);
+    *input = tail;
+    Ok(head)
+}
+
+macro_rules! impl_codec_for_int {
+    ($($int:ty),*) => {
+        $(
+            impl Encode for $int {
+                fn encode(&self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(&self.to_le_bytes());
+                }
+            }
+
+            impl Decode for $int {
+                fn decode(input: &mut &[u8]) -> Result<Self, SnapshotError> {
+                    let bytes = take(input, std::mem::size_of::<$int>())?;
+                    Ok(<$int>::from_le_bytes(bytes.try_into().unwrap()))
+                }
+            }
+        )*
+    };
+}
+
+impl_codec_for_int!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+/// usize записывается как u64, чтобы снимок не зависел от разрядности.
+impl Encode for usize {
+    fn encode(&self, out: &mut Vec<u8>) {
+        (*self as u64).encode(out);
+    }
+}
+
+impl Decode for usize {
+    fn decode(input: &mut &[u8]) -> Result<Self, SnapshotError> {
+        usize::try_from(u64::decode(input)?)
+            .map_err(|_| SnapshotError::Invalid("usize overflow".to_string()))
+    }
+}
+
+impl Encode for bool {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.push(u8::from(*self));
+    }
+}
+
+impl Decode for bool {
+    fn decode(input: &mut &[u8]) -> Result<Self, SnapshotError> {
+        match u8::decode(input)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            other => Err(SnapshotError::Invalid(format!("bool byte {}", other))),
+        }
+    }
+}
+
+impl Encode for String {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.len().encode(out);
+        out.extend_from_slice(self.as_bytes());
+    }
+}
+
+impl Decode for String {
+    fn decode(input: &mut &[u8]) -> Result<Self, SnapshotError> {
+        let len = usize::decode(input)?;
+        let bytes = take(input, len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|error| SnapshotError::Invalid(error.to_string()))
+    }
+}
+
+impl<T: Encode> Encode for Vec<T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.len().encode(out);
+        for item in self {
+            item.encode(out);
+        }
+    }
+}
+
+impl<T: Decode> Decode for Vec<T> {
+    fn decode(input: &mut &[u8]) -> Result<Self, SnapshotError> {
+        let len = usize::decode(input)?;
+        (0..len).map(|_| T::decode(input)).collect()
+    }
+}
+
+impl<A: Encode, B: Encode> Encode for (A, B) {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.0.encode(out);
+        self.1.encode(out);
+    }
+}
+
+impl<A: Decode, B: Decode> Decode for (A, B) {
+    fn decode(input: &mut &[u8]) -> Result<Self, SnapshotError> {
+        Ok((A::decode(input)?, B::decode(input)?))
+    }
+}
+
+/// CRC-32 (IEEE 802.3), побитовый вариант без таблицы.
+pub fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in bytes {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+/// Временный файл для сохранения: полное имя снимка с суффиксом ".tmp",
+/// чтобы снимки a.bin и a.dat не делили временный файл, а a.tmp не писался напрямую.
+fn temporary_path(path: &Path) -> Result<PathBuf, SnapshotError> {
+    let mut name = path
+        .file_name()
+        .ok_or_else(|| {
+            SnapshotError::Io(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "snapshot path has no file name",
+            ))
+        })?
+        .to_os_string();
+    name.push(".tmp");
+    Ok(path.with_file_name(name))
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> Result<u32, SnapshotError> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+impl<T, U, F, P> Memoized<T, U, F, P>
+where
+    T: Eq + Hash + Clone + Encode + Decode,
+    U: Clone + Encode + Decode,
+    F: Fn(T) -> U,
+    P: EvictionPolicy<T, U>,
+{
+    /// Записывает содержимое кеша. Возвращает число записанных записей.
+    pub fn write_snapshot<W: Write>(&self, writer: &mut W) -> Result<usize, SnapshotError> {
+        let mut header = Vec::with_capacity(16);
+        header.extend_from_slice(MAGIC);
+        header.extend_from_slice(&VERSION.to_le_bytes());
+        header.extend_from_slice(&(self.cache.len() as u64).to_le_bytes());
+        writer.write_all(&header)?;
+        writer.write_all(&crc32(&header).to_le_bytes())?;
+
+        let mut payload = Vec::new();
+        for (key, value) in self.cache.iter() {
+            payload.clear();
+            key.encode(&mut payload);
+            value.encode(&mut payload);
+            let len = u32::try_from(payload.len())
+                .map_err(|_| SnapshotError::Invalid("entry is too large".to_string()))?;
+            writer.write_all(&len.to_le_bytes())?;
+            writer.write_all(&payload)?;
+            writer.write_all(&crc32(&payload).to_le_bytes())?;
+        }
+        writer.flush()?;
+        Ok(self.cache.len())
+    }
+
+    /// Загружает записи снимка в кеш (с учетом политики вытеснения).
+    /// При ошибке кеш не меняется. Возвращает число прочитанных записей.
+    pub fn read_snapshot<R: Read>(&mut self, reader: &mut R) -> Result<usize, SnapshotError> {
+        let mut header = [0; 16];
+        reader.read_exact(&mut header[..4])?;
+        if &header[..4] != MAGIC {
+            return Err(SnapshotError::BadMagic);
+        }
+        reader.read_exact(&mut header[4..])?;
+        let version = u32::from_le_bytes(header[4..8].try_into().unwrap());
+        if version != VERSION {
+            return Err(SnapshotError::UnsupportedVersion(version));
+        }
+        // Число записей проверяется до того, как по нему начнется чтение.
+        if read_u32(reader)? != crc32(&header) {
+            return Err(SnapshotError::HeaderChecksum);
+        }
+        let count = u64::from_le_bytes(header[8..].try_into().unwrap());
+
+        let mut entries = Vec::new();
+        for entry in 0..count {
+            // Длину не доверяем: читаем не больше, чем есть в источнике.
+            let len = read_u32(reader)? as usize;
+            let mut payload = Vec::new();
+            reader.by_ref().take(len as u64).read_to_end(&mut payload)?;
+            if payload.len() != len {
+                return Err(SnapshotError::Truncated);
+            }
+            if read_u32(reader)? != crc32(&payload) {
+                return Err(SnapshotError::Checksum { entry });
+            }
+            let mut input = payload.as_slice();
+            let key = T::decode(&mut input)?;
+            let value = U::decode(&mut input)?;
+            if !input.is_empty() {
+                return Err(SnapshotError::Invalid(format!(
+                    "{} trailing bytes in entry {}",
+                    input.len(),
+                    entry
+                )));
+            }
+            entries.push((key, value));
+        }
+        if reader.read(&mut [0; 1])? != 0 {
+            return Err(SnapshotError::TrailingData);
+        }
+
+        let loaded = entries.len();
+        for (key, value) in entries {
+            self.cache.insert(key, value);
+        }
+        Ok(loaded)
+    }
+
+    /// Сохраняет снимок в файл. Запись идет во временный файл, который затем
+    /// переименовывается, поэтому прерванное сохранение не портит старый снимок.
+    pub fn save_snapshot<Q: AsRef<Path>>(&self, path: Q) -> Result<usize, SnapshotError> {
+        let path = path.as_ref();
+        let temporary = temporary_path(path)?;
+        let saved = {
+            let mut writer = BufWriter::new(File::create(&temporary)?);
+            self.write_snapshot(&mut writer)?
+        };
+        fs::rename(&temporary, path)?;
+        Ok(saved)
+    }
+
+    /// Загружает снимок из файла.
+    pub fn load_snapshot<Q: AsRef<Path>>(&mut self, path: Q) -> Result<usize, SnapshotError> {
+        let mut reader = BufReader::new(File::open(path)?);
+        self.read_snapshot(&mut reader)
+    }
+}
+
+#[test]
+fn test_memoization_snapshot() {
+    use super::memoize;
+    use std::cell::Cell;
+    use std::process;
+
+    let calls = Cell::new(0);
+    let expensive = |n: u64| {
+        calls.set(calls.get() + 1);
+        format!("result-{}", n * n)
+    };
+
+    // Первый запуск: вычисляем и сохраняем
+    let path = std::env::temp_dir().join(format!("memo-snapshot-{}.bin", process::id()));
+    let mut first_run = memoize(expensive);
+    for n in 1..=3 {
+        first_run.call(n);
+    }
+    assert_eq!(first_run.save_snapshot(&path).unwrap(), 3);
+    assert_eq!(calls.get(), 3);
+
+    // Второй запуск: теплый старт из снимка, без вычислений
+    let mut second_run = memoize(expensive);
+    assert_eq!(second_run.load_snapshot(&path).unwrap(), 3);
+    assert_eq!(second_run.call(2), "result-4");
+    assert_eq!(calls.get(), 3);
+    fs::remove_file(&path).unwrap();
+
+    // Временный файл получает суффикс к полному имени снимка
+    let temporary = |name: &str| temporary_path(Path::new(name)).unwrap();
+    assert_eq!(temporary("cache/a.bin"), Path::new("cache/a.bin.tmp"));
+    assert_ne!(temporary("a.bin"), temporary("a.dat"));
+    assert_eq!(temporary("a.tmp"), Path::new("a.tmp.tmp"));
+    assert!(temporary_path(Path::new("/")).is_err());
+
+    // Снимок в памяти для проверок повреждений
+    let mut bytes = Vec::new();
+    first_run.write_snapshot(&mut bytes).unwrap();
+    let load = |bytes: &[u8]| memoize(|n: u64| n.to_string()).read_snapshot(&mut &bytes[..]);
+    assert_eq!(load(&bytes).unwrap(), 3);
+
+    // Испорченный байт в данных записи
+    let mut corrupted = bytes.clone();
+    corrupted[20 + 4] ^= 0xFF;
+    assert!(matches!(
+        load(&corrupted),
+        Err(SnapshotError::Checksum { entry: 0 })
+    ));
+
+    // Испорченное число записей в заголовке
+    let mut corrupted = bytes.clone();
+    corrupted[8] ^= 0x01;
+    assert!(matches!(
+        load(&corrupted),
+        Err(SnapshotError::HeaderChecksum)
+    ));
+
+    // Лишние байты после последней записи
+    let mut extended = bytes.clone();
+    extended.push(0);
+    assert!(matches!(load(&extended), Err(SnapshotError::TrailingData)));
+
+    // Обрезанный файл
+    assert!(matches!(
+        load(&bytes[..bytes.len() - 2]),
+        Err(SnapshotError::Truncated)
+    ));
+
+    // Чужой файл и другая версия формата
+    let mut foreign = bytes.clone();
+    foreign[0] = b'X';
+    assert!(matches!(load(&foreign), Err(SnapshotError::BadMagic)));
+    let mut future = bytes.clone();
+    future[4..8].copy_from_slice(&3u32.to_le_bytes());
+    assert!(matches!(
+        load(&future),
+        Err(SnapshotError::UnsupportedVersion(3))
+    ));
+
+    // Известное значение CRC-32
+    assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+}
@@ -0,0 +1,155 @@
+// Потокобезопасная мемоизация с дедупликацией одновременных вычислений (single-flight).
+// Кеш разбит на шарды со своими блокировками, поэтому обращения к разным ключам
+// не мешают друг другу. Для каждого ключа хранится OnceLock: если несколько потоков
+// запрашивают один и тот же ключ, вычисляет его только один, остальные ждут результата.
+
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+
+use super::CacheStats;
+
+const DEFAULT_SHARDS: usize = 16;
+
+type Shard<T, U> = Mutex<HashMap<T, Arc<OnceLock<U>>>>;
+
+/// Мемоизация, которую можно вызывать через &self из многих потоков.
+pub struct SyncMemoized<T, U, F>
+where
+    T: Eq + Hash + Clone,
+    U: Clone,
+    F: Fn(T) -> U + Sync,
+{
+    func: F,
+    shards: Vec<Shard<T, U>>,
+    hasher: RandomState,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl<T, U, F> SyncMemoized<T, U, F>
+where
+    T: Eq + Hash + Clone,
+    U: Clone,
+    F: Fn(T) -> U + Sync,
+{
+    pub fn new(func: F) -> Self {
+        SyncMemoized::with_shards(func, DEFAULT_SHARDS)
+    }
+
+    /// Создает мемоизацию с заданным числом шардов.
+    pub fn with_shards(func: F, shards: usize) -> Self {
+        SyncMemoized {
+            func,
+            shards: (0..shards.max(1))
+                .map(|_| Mutex::new(HashMap::new()))
+                .collect(),
+            hasher: RandomState::new(),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Вызывает функцию с мемоизацией. Блокировка шарда держится только на время
+    /// поиска ячейки, само вычисление идет без нее.
+    pub fn call(&self, arg: T) -> U {
+        let cell = {
+            let mut shard = self.shard(&arg).lock().unwrap();
+            Arc::clone(shard.entry(arg.clone()).or_default())
+        };
+
+        let mut computed = false;
+        let result = cell.get_or_init(|| {
+            computed = true;
+            (self.func)(arg)
+        });
+        if computed {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+        }
+        result.clone()
+    }
+
+    /// Возвращает число закешированных значений.
+    pub fn cache_size(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| {
+                let shard = shard.lock().unwrap();
+                shard.values().filter(|cell| cell.get().is_some()).count()
+            })
+            .sum()
+    }
+
+    /// Возвращает статистику. Поток, дождавшийся чужого вычисления, считается попаданием.
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: 0,
+        }
+    }
+
+    fn shard(&self, key: &T) -> &Shard<T, U> {
+        let index = self.hasher.hash_one(key) as usize % self.shards.len();
+        &self.shards[index]
+    }
+}
+
+/// Вызывает мемоизированную функцию для каждого аргумента в отдельном потоке.
+pub fn call_parallel<T, U, F>(memoized: &SyncMemoized<T, U, F>, args: Vec<T>) -> Vec<U>
+where
+    T: Eq + Hash + Clone + Send,
+    U: Clone + Send + Sync,
+    F: Fn(T) -> U + Sync,
+{
+    thread::scope(|scope| {
+        let handles: Vec<_> = args
+            .into_iter()
+            .map(|arg| scope.spawn(move || memoized.call(arg)))
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect()
+    })
+}
+
+#[test]
+fn test_sync_memoization() {
+    use std::sync::atomic::AtomicUsize;
+    use std::time::Duration;
+
+    let computations = AtomicUsize::new(0);
+    let slow_square = SyncMemoized::new(|x: u64| {
+        computations.fetch_add(1, Ordering::SeqCst);
+        thread::sleep(Duration::from_millis(20));
+        x * x
+    });
+
+    // Восемь потоков одновременно просят один ключ - вычисление одно
+    let results = call_parallel(&slow_square, vec![7; 8]);
+    assert_eq!(results, vec![49; 8]);
+    assert_eq!(computations.load(Ordering::SeqCst), 1);
+    assert_eq!(slow_square.stats().misses, 1);
+    assert_eq!(slow_square.stats().hits, 7);
+
+    // Разные ключи вычисляются параллельно и независимо
+    let results = call_parallel(&slow_square, (1..=4).collect());
+    assert_eq!(results, vec![1, 4, 9, 16]);
+    assert_eq!(computations.load(Ordering::SeqCst), 5);
+    assert_eq!(slow_square.cache_size(), 5);
+
+    // Повторный вызов - из кеша
+    assert_eq!(slow_square.call(3), 9);
+    assert_eq!(computations.load(Ordering::SeqCst), 5);
+
+    // Один шард тоже работает корректно
+    let single = SyncMemoized::with_shards(|x: u64| x + 1, 1);
+    assert_eq!(call_parallel(&single, vec![1, 2, 1, 2]), vec![2, 3, 2, 3]);
+    assert_eq!(single.cache_size(), 2);
+}
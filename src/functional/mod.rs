@@ -1,7 +1,19 @@
 pub mod applicative;
+pub mod clock;
 pub mod composition;
 pub mod currying;
+pub mod effects;
+pub mod foldable;
 pub mod functor;
+pub mod lift;
 pub mod memoization;
+pub mod monad;
+pub mod optics;
 pub mod options;
-pub mod promise;
\ No newline at end of file
+pub mod persistent;
+pub mod promise;
+pub mod property;
+pub mod random;
+pub mod semigroup;
+pub mod stream;
+pub mod validation;
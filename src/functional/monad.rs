@@ -0,0 +1,221 @@
+// Функциональный паттерн Monad: аппликативный функтор, который умеет связывать вычисления.
+// bind передает значение из контекста в функцию, которая сама возвращает новый контекст,
+// и "склеивает" контексты. Так строятся последовательные вычисления, где каждый шаг
+// зависит от предыдущего: цепочки Option без вложенных match, перебор вариантов в Vec.
+// Promise вызывает продолжение позже и, возможно, в другом потоке, поэтому реализует
+// SendMonad: ту же форму с ограничениями Send + 'static (см. promise::monad).
+// Макрос mdo! дает do-нотацию: шаги `x <- m;` превращаются во вложенные вызовы bind.
+
+use super::applicative::{AppResult, Applicative};
+use super::functor::Maybe;
+
+/// Трейт для монады. 'f - время жизни функции, как в Functor.
+pub trait Monad<'f, T>: Applicative<'f, T> + Sized {
+    fn bind<U, F>(self, f: F) -> Self::Output<U>
+    where
+        F: Fn(T) -> Self::Output<U> + 'f;
+
+    /// Синоним bind.
+    fn flat_map<U, F>(self, f: F) -> Self::Output<U>
+    where
+        F: Fn(T) -> Self::Output<U> + 'f,
+    {
+        self.bind(f)
+    }
+}
+
+/// Монада для контекстов, которые хранят продолжение до появления значения и могут
+/// вызвать его в другом потоке. Функция должна быть Send + 'static, а значение -
+/// еще и Clone: результат получает каждый подписчик.
+pub trait SendMonad<T>: Sized {
+    type Output<U>;
+
+    fn pure(value: T) -> Self;
+
+    fn bind<U, F>(self, f: F) -> Self::Output<U>
+    where
+        U: Clone + Send + 'static,
+        F: Fn(T) -> Self::Output<U> + Send + 'static;
+
+    /// Синоним bind.
+    fn flat_map<U, F>(self, f: F) -> Self::Output<U>
+    where
+        U: Clone + Send + 'static,
+        F: Fn(T) -> Self::Output<U> + Send + 'static,
+    {
+        self.bind(f)
+    }
+}
+
+/// Реализация монады для Option.
+impl<'f, T> Monad<'f, T> for Option<T> {
+    fn bind<U, F>(self, f: F) -> Option<U>
+    where
+        F: Fn(T) -> Option<U> + 'f,
+    {
+        match self {
+            Some(value) => f(value),
+            None => None,
+        }
+    }
+}
+
+/// Реализация монады для Vec: результаты функции для всех значений склеиваются.
+impl<'f, T: Clone> Monad<'f, T> for Vec<T> {
+    fn bind<U, F>(self, f: F) -> Vec<U>
+    where
+        F: Fn(T) -> Vec<U> + 'f,
+    {
+        self.into_iter().flat_map(f).collect()
+    }
+}
+
+/// Реализация монады для Maybe.
+impl<'f, T> Monad<'f, T> for Maybe<T> {
+    fn bind<U, F>(self, f: F) -> Maybe<U>
+    where
+        F: Fn(T) -> Maybe<U> + 'f,
+    {
+        match self.into_option() {
+            Some(value) => f(value),
+            None => Maybe::none(),
+        }
+    }
+}
+
+/// Реализация монады для AppResult: первая ошибка прерывает цепочку.
+impl<'f, T, E> Monad<'f, T> for AppResult<T, E> {
+    fn bind<U, F>(self, f: F) -> AppResult<U, E>
+    where
+        F: Fn(T) -> AppResult<U, E> + 'f,
+    {
+        match self.into_result() {
+            Ok(value) => f(value),
+            Err(e) => AppResult::err(e),
+        }
+    }
+}
+
+/// Do-нотация для любой монады.
+///
+/// Шаг `x <- m;` связывает значение монады с именем x, `let p = e;` - обычное связывание,
+/// `m;` - шаг, значение которого не нужно. Последним идет выражение-результат.
+/// Каждый следующий шаг выполняется внутри замыкания, поэтому связанные значения,
+/// используемые дальше чем на один шаг, должны быть Copy (или клонироваться явно).
+///
+/// ```ignore
+/// let sum = mdo! {
+///     x <- Some(1);
+///     y <- Some(2);
+///     let z = x + y;
+///     Some(z * 10)
+/// };
+/// ```
+#[macro_export]
+macro_rules! mdo {
+    (let $p:pat = $e:expr; $($rest:tt)+) => {{
+        let $p = $e;
+        $crate::mdo!($($rest)+)
+    }};
+    ($x:ident <- $m:expr; $($rest:tt)+) => {
+        $crate::functional::monad::Monad::bind($m, move |$x| {
+            $crate::mdo!($($rest)+)
+        })
+    };
+    ($m:expr; $($rest:tt)+) => {
+        $crate::functional::monad::Monad::bind($m, move |_| {
+            $crate::mdo!($($rest)+)
+        })
+    };
+    ($result:expr) => {
+        $result
+    };
+}
+
+#[cfg(test)]
+fn assert_monad_laws<M>(m: M, a: i32, f: fn(i32) -> M, g: fn(i32) -> M)
+where
+    M: Monad<'static, i32, Output<i32> = M> + Clone + PartialEq + std::fmt::Debug + 'static,
+{
+    // Левая единица: pure(a) >>= f == f(a)
+    assert_eq!(M::pure(a).bind::<i32, _>(f), f(a));
+    // Правая единица: m >>= pure == m
+    assert_eq!(m.clone().bind::<i32, _>(M::pure), m);
+    // Ассоциативность: (m >>= f) >>= g == m >>= (x -> f(x) >>= g)
+    assert_eq!(
+        m.clone().bind::<i32, _>(f).bind::<i32, _>(g),
+        m.bind::<i32, _>(move |x| f(x).bind::<i32, _>(g))
+    );
+}
+
+#[test]
+fn test_monad() {
+    // Законы монады
+    assert_monad_laws(
+        Some(3),
+        5,
+        |x| Some(x + 1),
+        |x| (x % 2 == 0).then_some(x / 2),
+    );
+    assert_monad_laws(None, 5, |x| Some(x + 1), |_| None);
+    assert_monad_laws(vec![1, 2], 3, |x| vec![x, -x], |x| vec![x * 10; 2]);
+    assert_monad_laws(Vec::new(), 3, |x| vec![x], |_| Vec::new());
+    assert_monad_laws(Maybe::some(7), 1, |x| Maybe::some(x * 2), |_| Maybe::none());
+    assert_monad_laws(
+        AppResult::<i32, String>::ok(10),
+        0,
+        |x| {
+            if x == 0 {
+                AppResult::err("division by zero".to_string())
+            } else {
+                AppResult::ok(100 / x)
+            }
+        },
+        |x| AppResult::ok(x - 1),
+    );
+    assert_monad_laws(
+        AppResult::err("early".to_string()),
+        4,
+        |x| AppResult::ok(x * 3),
+        |x| AppResult::ok(x + 3),
+    );
+
+    // flat_map - синоним bind
+    assert_eq!(Some("42").flat_map(|s| s.parse::<i32>().ok()), Some(42));
+
+    // Do-нотация над Option
+    let parse = |s: &str| s.parse::<i32>().ok();
+    let sum = mdo! {
+        x <- parse("1");
+        y <- parse("2");
+        let z = x + y;
+        Some(z * 10)
+    };
+    assert_eq!(sum, Some(30));
+    let failed = mdo! {
+        x <- parse("1");
+        y <- parse("two");
+        Some(x + y)
+    };
+    assert_eq!(failed, None);
+
+    // Над Vec: все пифагоровы тройки со сторонами до 13
+    let triples = mdo! {
+        a <- (1..=13).collect::<Vec<u32>>();
+        b <- (a..=13).collect::<Vec<u32>>();
+        c <- (b..=13).collect::<Vec<u32>>();
+        if a * a + b * b == c * c { vec![(a, b, c)] } else { Vec::new() }
+    };
+    assert_eq!(triples, vec![(3, 4, 5), (5, 12, 13), (6, 8, 10)]);
+
+    // Шаг без имени: значение не используется, но ошибка прерывает цепочку
+    let checked = |x: i32| {
+        mdo! {
+            AppResult::<(), &str>::ok(());
+            limit <- if x < 100 { AppResult::ok(x) } else { AppResult::err("too big") };
+            AppResult::ok(limit * 2)
+        }
+    };
+    assert_eq!(checked(21), AppResult::ok(42));
+    assert_eq!(checked(500), AppResult::err("too big"));
+}
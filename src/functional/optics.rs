@@ -0,0 +1,448 @@
+// Функциональный паттерн Optics: линзы, призмы и обходы для неизменяемого обновления
+// вложенных структур.
+// Lens фокусируется на поле, которое есть всегда, Prism - на варианте перечисления,
+// которого может не быть, Traversal - на нескольких значениях сразу (например, элементах Vec).
+// Оптики соединяются методом then, а их функции view, set_to и over - обычные функции,
+// которые соединяются через composition::compose и pipe.
+// Макрос lenses! генерирует модуль с линзами для полей структуры, prism! - призму для
+// варианта перечисления с одним полем.
+// Пример: замена двигателя автомобиля внутри гаража.
+
+use std::rc::Rc;
+
+type GetFn<S, A> = Rc<dyn Fn(&S) -> &A>;
+type PreviewFn<S, A> = Rc<dyn Fn(&S) -> Option<&A>>;
+type GetAllFn<S, A> = Rc<dyn Fn(&S) -> Vec<&A>>;
+type SetFn<S, A> = Rc<dyn Fn(S, A) -> S>;
+type ReviewFn<S, A> = Rc<dyn Fn(A) -> S>;
+type ModifyFn<S, A> = Rc<dyn Fn(S, &dyn Fn(A) -> A) -> S>;
+
+/// Линза: чтение и замена поля A внутри S.
+pub struct Lens<S, A> {
+    get: GetFn<S, A>,
+    set: SetFn<S, A>,
+}
+
+impl<S, A> Clone for Lens<S, A> {
+    fn clone(&self) -> Self {
+        Lens {
+            get: Rc::clone(&self.get),
+            set: Rc::clone(&self.set),
+        }
+    }
+}
+
+impl<S: 'static, A: 'static> Lens<S, A> {
+    pub fn new<G, F>(get: G, set: F) -> Self
+    where
+        G: Fn(&S) -> &A + 'static,
+        F: Fn(S, A) -> S + 'static,
+    {
+        Lens {
+            get: Rc::new(get),
+            set: Rc::new(set),
+        }
+    }
+
+    pub fn get<'a>(&self, source: &'a S) -> &'a A {
+        (self.get)(source)
+    }
+
+    /// Возвращает S с замененным полем.
+    pub fn set(&self, source: S, value: A) -> S {
+        (self.set)(source, value)
+    }
+
+    /// Линза на поле B внутри поля A.
+    pub fn then<B: 'static>(self, inner: Lens<A, B>) -> Lens<S, B>
+    where
+        A: Clone,
+    {
+        let outer = self.clone();
+        let get_inner = inner.clone();
+        Lens::new(
+            move |source: &S| get_inner.get(self.get(source)),
+            move |source: S, value: B| {
+                let part = inner.set(outer.get(&source).clone(), value);
+                outer.set(source, part)
+            },
+        )
+    }
+
+    /// Обход, фокусирующийся на единственном поле линзы.
+    pub fn into_traversal(self) -> Traversal<S, A>
+    where
+        A: Clone,
+    {
+        let modifier = self.clone();
+        Traversal::new(
+            move |source: &S| vec![self.get(source)],
+            move |source: S, f: &dyn Fn(A) -> A| modifier.modify(source, f),
+        )
+    }
+}
+
+impl<S: 'static, A: Clone + 'static> Lens<S, A> {
+    /// Применяет функцию к полю.
+    pub fn modify<F>(&self, source: S, f: F) -> S
+    where
+        F: FnOnce(A) -> A,
+    {
+        let value = f(self.get(&source).clone());
+        self.set(source, value)
+    }
+
+    /// Функция чтения поля.
+    pub fn view(&self) -> impl Fn(&S) -> A {
+        let lens = self.clone();
+        move |source| lens.get(source).clone()
+    }
+
+    /// Функция, заменяющая поле значением value.
+    pub fn set_to(&self, value: A) -> impl Fn(S) -> S {
+        let lens = self.clone();
+        move |source| lens.set(source, value.clone())
+    }
+
+    /// Функция, применяющая f к полю.
+    pub fn over<F>(&self, f: F) -> impl Fn(S) -> S
+    where
+        F: Fn(A) -> A,
+    {
+        let lens = self.clone();
+        move |source| lens.modify(source, &f)
+    }
+}
+
+/// Призма: вариант A внутри S, которого может не быть.
+pub struct Prism<S, A> {
+    preview: PreviewFn<S, A>,
+    review: ReviewFn<S, A>,
+}
+
+impl<S, A> Clone for Prism<S, A> {
+    fn clone(&self) -> Self {
+        Prism {
+            preview: Rc::clone(&self.preview),
+            review: Rc::clone(&self.review),
+        }
+    }
+}
+
+impl<S: 'static, A: 'static> Prism<S, A> {
+    pub fn new<P, R>(preview: P, review: R) -> Self
+    where
+        P: Fn(&S) -> Option<&A> + 'static,
+        R: Fn(A) -> S + 'static,
+    {
+        Prism {
+            preview: Rc::new(preview),
+            review: Rc::new(review),
+        }
+    }
+
+    /// Значение варианта, если S - этот вариант.
+    pub fn preview<'a>(&self, source: &'a S) -> Option<&'a A> {
+        (self.preview)(source)
+    }
+
+    /// Строит S из значения варианта.
+    pub fn review(&self, value: A) -> S {
+        (self.review)(value)
+    }
+
+    /// Заменяет значение, если S - этот вариант, иначе возвращает S без изменений.
+    pub fn set(&self, source: S, value: A) -> S {
+        if self.preview(&source).is_some() {
+            self.review(value)
+        } else {
+            source
+        }
+    }
+
+    /// Призма на вариант B внутри варианта A.
+    pub fn then<B: 'static>(self, inner: Prism<A, B>) -> Prism<S, B> {
+        let outer = self.clone();
+        let preview_inner = inner.clone();
+        Prism::new(
+            move |source: &S| {
+                self.preview(source)
+                    .and_then(|part| preview_inner.preview(part))
+            },
+            move |value: B| outer.review(inner.review(value)),
+        )
+    }
+
+    /// Обход, фокусирующийся на значении варианта, если оно есть.
+    pub fn into_traversal(self) -> Traversal<S, A>
+    where
+        A: Clone,
+    {
+        let modifier = self.clone();
+        Traversal::new(
+            move |source: &S| self.preview(source).into_iter().collect(),
+            move |source: S, f: &dyn Fn(A) -> A| modifier.modify(source, f),
+        )
+    }
+}
+
+impl<S: 'static, A: Clone + 'static> Prism<S, A> {
+    /// Применяет функцию к значению варианта, если S - этот вариант.
+    pub fn modify<F>(&self, source: S, f: F) -> S
+    where
+        F: FnOnce(A) -> A,
+    {
+        match self.preview(&source).cloned() {
+            Some(value) => self.review(f(value)),
+            None => source,
+        }
+    }
+
+    /// Функция, применяющая f к значению варианта.
+    pub fn over<F>(&self, f: F) -> impl Fn(S) -> S
+    where
+        F: Fn(A) -> A,
+    {
+        let prism = self.clone();
+        move |source| prism.modify(source, &f)
+    }
+}
+
+/// Обход: несколько значений A внутри S.
+pub struct Traversal<S, A> {
+    get_all: GetAllFn<S, A>,
+    modify: ModifyFn<S, A>,
+}
+
+impl<S, A> Clone for Traversal<S, A> {
+    fn clone(&self) -> Self {
+        Traversal {
+            get_all: Rc::clone(&self.get_all),
+            modify: Rc::clone(&self.modify),
+        }
+    }
+}
+
+impl<A: 'static> Traversal<Vec<A>, A> {
+    /// Обход всех элементов вектора.
+    pub fn each() -> Self {
+        Traversal::new(
+            |items: &Vec<A>| items.iter().collect(),
+            |items: Vec<A>, f: &dyn Fn(A) -> A| items.into_iter().map(f).collect(),
+        )
+    }
+}
+
+impl<S: 'static, A: 'static> Traversal<S, A> {
+    pub fn new<G, M>(get_all: G, modify: M) -> Self
+    where
+        G: Fn(&S) -> Vec<&A> + 'static,
+        M: Fn(S, &dyn Fn(A) -> A) -> S + 'static,
+    {
+        Traversal {
+            get_all: Rc::new(get_all),
+            modify: Rc::new(modify),
+        }
+    }
+
+    /// Все значения в порядке обхода.
+    pub fn get_all<'a>(&self, source: &'a S) -> Vec<&'a A> {
+        (self.get_all)(source)
+    }
+
+    /// Применяет функцию к каждому значению.
+    pub fn modify<F>(&self, source: S, f: F) -> S
+    where
+        F: Fn(A) -> A,
+    {
+        (self.modify)(source, &f)
+    }
+
+    /// Обход значений B внутри каждого значения A.
+    pub fn then<B: 'static>(self, inner: Traversal<A, B>) -> Traversal<S, B> {
+        let outer = self.clone();
+        let get_inner = inner.clone();
+        Traversal::new(
+            move |source: &S| {
+                self.get_all(source)
+                    .into_iter()
+                    .flat_map(|part| get_inner.get_all(part))
+                    .collect()
+            },
+            move |source: S, f: &dyn Fn(B) -> B| outer.modify(source, |part| inner.modify(part, f)),
+        )
+    }
+
+    /// Функция, применяющая f к каждому значению.
+    pub fn over<F>(&self, f: F) -> impl Fn(S) -> S
+    where
+        F: Fn(A) -> A,
+    {
+        let traversal = self.clone();
+        move |source| traversal.modify(source, &f)
+    }
+}
+
+impl<S: 'static, A: Clone + 'static> Traversal<S, A> {
+    /// Заменяет каждое значение на value.
+    pub fn set_all(&self, source: S, value: A) -> S {
+        self.modify(source, move |_| value.clone())
+    }
+}
+
+/// Генерирует модуль с линзами для полей структуры:
+/// lenses! { pub mod car_lens for Car { engine: String, seats: u32 } },
+/// после чего car_lens::engine() - линза Lens<Car, String>.
+#[macro_export]
+macro_rules! lenses {
+    ($(#[$meta:meta])* $vis:vis mod $module:ident for $target:ident {
+        $($field:ident: $value:ty),* $(,)?
+    }) => {
+        $(#[$meta])*
+        $vis mod $module {
+            #[allow(unused_imports)]
+            use super::*;
+
+            $(
+                pub fn $field() -> $crate::functional::optics::Lens<$target, $value> {
+                    $crate::functional::optics::Lens::new(
+                        |source: &$target| &source.$field,
+                        |mut source: $target, value: $value| {
+                            source.$field = value;
+                            source
+                        },
+                    )
+                }
+            )*
+        }
+    };
+}
+
+/// Призма для варианта перечисления с одним полем: prism!(Shape::Circle).
+#[macro_export]
+macro_rules! prism {
+    ($($variant:ident)::+) => {
+        $crate::functional::optics::Prism::new(
+            |source| match source {
+                $($variant)::+(value) => Some(value),
+                #[allow(unreachable_patterns)]
+                _ => None,
+            },
+            $($variant)::+,
+        )
+    };
+}
+
+#[cfg(test)]
+use crate::gang_of_four::creational::builder::Car;
+
+// Линзы для автомобиля из creational::builder, нужные только в тесте.
+#[cfg(test)]
+crate::lenses! {
+    mod car_lens for Car {
+        seats: u32,
+        engine: String,
+        gps: bool,
+    }
+}
+
+#[test]
+fn test_optics() {
+    use super::composition::{compose, pipe};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Garage {
+        owner: String,
+        car: Car,
+    }
+
+    // Линзы для локальной структуры задаются вручную
+    let garage_owner = Lens::new(
+        |garage: &Garage| &garage.owner,
+        |garage, owner| Garage { owner, ..garage },
+    );
+    let garage_car = Lens::new(
+        |garage: &Garage| &garage.car,
+        |garage, car| Garage { car, ..garage },
+    );
+
+    let garage = Garage {
+        owner: "Ann".to_string(),
+        car: Car {
+            seats: 2,
+            engine: "SportEngine".to_string(),
+            trip_computer: true,
+            gps: true,
+        },
+    };
+
+    // Составная линза обновляет вложенное поле, не трогая остальные
+    let engine = garage_car.clone().then(car_lens::engine());
+    assert_eq!(engine.get(&garage), "SportEngine");
+    let upgraded = engine.set(garage.clone(), "V8".to_string());
+    assert_eq!(upgraded.car.engine, "V8");
+    assert_eq!(upgraded.car.seats, 2);
+    assert_eq!(upgraded.owner, "Ann");
+    assert_eq!(garage.car.engine, "SportEngine");
+
+    // Функции линз соединяются через compose и pipe
+    let tune = pipe(
+        engine.over(|name: String| format!("Turbo{}", name)),
+        garage_car
+            .clone()
+            .then(car_lens::seats())
+            .over(|seats| seats + 2),
+    );
+    let tuned = tune(garage.clone());
+    assert_eq!(tuned.car.engine, "TurboSportEngine");
+    assert_eq!(tuned.car.seats, 4);
+    let engine_length = compose(|name: String| name.len(), engine.view());
+    assert_eq!(engine_length(&tuned), 16);
+    let handover = compose(
+        garage_owner.set_to("Bob".to_string()),
+        garage_car.clone().then(car_lens::gps()).set_to(false),
+    );
+    let handed = handover(garage.clone());
+    assert_eq!((handed.owner.as_str(), handed.car.gps), ("Bob", false));
+
+    // Призма работает только с нужным вариантом
+    #[derive(Debug, Clone, PartialEq)]
+    enum Shape {
+        Circle(f64),
+        Square(f64),
+    }
+    let circle = crate::prism!(Shape::Circle);
+    assert_eq!(circle.preview(&Shape::Circle(1.0)), Some(&1.0));
+    assert_eq!(circle.preview(&Shape::Square(1.0)), None);
+    assert_eq!(circle.review(2.0), Shape::Circle(2.0));
+    let grow = circle.over(|r| r * 2.0);
+    assert_eq!(grow(Shape::Circle(1.5)), Shape::Circle(3.0));
+    assert_eq!(grow(Shape::Square(1.5)), Shape::Square(1.5));
+    assert_eq!(circle.set(Shape::Circle(1.0), 7.0), Shape::Circle(7.0));
+    assert_eq!(circle.set(Shape::Square(1.0), 7.0), Shape::Square(1.0));
+    let some_circle = crate::prism!(Some).then(circle.clone());
+    assert_eq!(some_circle.preview(&Some(Shape::Circle(4.0))), Some(&4.0));
+    assert_eq!(some_circle.review(5.0), Some(Shape::Circle(5.0)));
+
+    // Обход изменяет поле у всех машин парка
+    let fleet = vec![garage.clone(), upgraded.clone()];
+    let engines = Traversal::each().then(engine.clone().into_traversal());
+    assert_eq!(engines.get_all(&fleet), ["SportEngine", "V8"]);
+    let electric = engines.set_all(fleet.clone(), "Electric".to_string());
+    assert!(electric.iter().all(|g| g.car.engine == "Electric"));
+    assert_eq!(electric[1].owner, "Ann");
+
+    // Обход через призму пропускает другие варианты
+    let radii = Traversal::each().then(circle.into_traversal());
+    let shapes = vec![Shape::Circle(1.0), Shape::Square(2.0), Shape::Circle(3.0)];
+    assert_eq!(radii.get_all(&shapes), [&1.0, &3.0]);
+    assert_eq!(
+        radii.modify(shapes.clone(), |r| r + 1.0),
+        vec![Shape::Circle(2.0), Shape::Square(2.0), Shape::Circle(4.0)]
+    );
+    let shrink = radii.over(|r| r / 2.0);
+    assert_eq!(
+        shrink(shapes),
+        vec![Shape::Circle(0.5), Shape::Square(2.0), Shape::Circle(1.5)]
+    );
+}
@@ -0,0 +1,492 @@
+// Функциональный паттерн Options: позволяет конфигурировать объекты с помощью функций-опций.
+// Полезен для создания объектов с множеством опциональных параметров.
+// Опция знает свое имя, поле и значение, поэтому сборка проверяет значения,
+// находит повторы и противоречия и возвращает все ошибки сразу.
+// Макрос options! генерирует функции-опции для полей любой структуры,
+// реализующей трейт Options, - так же настраиваются и конфигурации сервисов.
+// Простые HouseOption ничего не проверяют: House::new применяет их по порядку,
+// и побеждает последняя; проверенные опции собирает House::try_new.
+// Наборы опций (пресеты) и объяснение собранного значения - в модуле preset.
+// Пример: конфигурация дома с различными опциями.
+
+pub mod preset;
+
+use std::fmt;
+use std::rc::Rc;
+
+use super::semigroup::NonEmpty;
+use preset::{Configured, Preset};
+
+const DEFAULT_FLOORS: i32 = 2;
+const DEFAULT_HAS_FIREPLACE: bool = true;
+const DEFAULT_MATERIAL: &str = "wood";
+
+/// Применение опции: изменяет значение или возвращает сообщение об ошибке.
+type ApplyFn<T> = Rc<dyn Fn(&mut T) -> Result<(), String>>;
+
+/// Чтение поля опции: отладочное представление текущего значения поля.
+type ReadFn<T> = Rc<dyn Fn(&T) -> String>;
+
+/// Функция-опция для значения типа T.
+/// Клонирование дешевое: функции опции разделяются между копиями.
+pub struct Opt<T> {
+    name: &'static str,
+    field: &'static str,
+    value: String,
+    apply: ApplyFn<T>,
+    read: ReadFn<T>,
+    preset: Option<String>,
+}
+
+impl<T> Opt<T> {
+    /// Создает опцию с именем name, которая устанавливает поле field;
+    /// value - отладочное представление значения, read читает поле в том же виде.
+    pub fn new<F, R>(
+        name: &'static str,
+        field: &'static str,
+        value: String,
+        apply: F,
+        read: R,
+    ) -> Self
+    where
+        F: Fn(&mut T) -> Result<(), String> + 'static,
+        R: Fn(&T) -> String + 'static,
+    {
+        Opt {
+            name,
+            field,
+            value,
+            apply: Rc::new(apply),
+            read: Rc::new(read),
+            preset: None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn field(&self) -> &'static str {
+        self.field
+    }
+
+    /// Отладочное представление значения.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Имя пресета, из которого взята опция.
+    pub fn preset(&self) -> Option<&str> {
+        self.preset.as_deref()
+    }
+
+    /// Применяет опцию к значению.
+    pub fn apply(&self, target: &mut T) -> Result<(), String> {
+        (self.apply)(target)
+    }
+
+    /// Читает поле опции из значения.
+    pub fn read(&self, target: &T) -> String {
+        (self.read)(target)
+    }
+
+    /// Помечает опцию как часть пресета.
+    fn in_preset(mut self, preset: &str) -> Self {
+        self.preset = Some(preset.to_string());
+        self
+    }
+}
+
+impl<T> Clone for Opt<T> {
+    fn clone(&self) -> Self {
+        Opt {
+            name: self.name,
+            field: self.field,
+            value: self.value.clone(),
+            apply: Rc::clone(&self.apply),
+            read: Rc::clone(&self.read),
+            preset: self.preset.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for Opt<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl<T> fmt::Display for Opt<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}({})", self.name, self.value)
+    }
+}
+
+/// Ошибка сборки значения из опций.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OptionError {
+    /// Опция отклонила значение.
+    Invalid { option: String, message: String },
+    /// Поле установлено повторно тем же значением.
+    Duplicate { option: String, field: &'static str },
+    /// Поле установлено разными значениями.
+    Conflict {
+        field: &'static str,
+        first: String,
+        second: String,
+    },
+    /// Собранное значение не прошло общую проверку.
+    Rejected(String),
+}
+
+impl fmt::Display for OptionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OptionError::Invalid { option, message } => write!(f, "{}: {}", option, message),
+            OptionError::Duplicate { option, field } => {
+                write!(f, "{}: field {} is already set", option, field)
+            }
+            OptionError::Conflict {
+                field,
+                first,
+                second,
+            } => write!(f, "{} conflicts with {} on field {}", second, first, field),
+            OptionError::Rejected(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+/// Трейт для значения, которое собирается из опций.
+pub trait Options: Sized {
+    /// Значение до применения опций.
+    fn defaults() -> Self;
+
+    /// Проверка собранного значения целиком, например согласованности полей.
+    fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Применяет опции к значению по умолчанию и собирает все ошибки.
+    fn build(options: Vec<Opt<Self>>) -> Result<Self, NonEmpty<OptionError>> {
+        Self::configure(options).map(Configured::into_value)
+    }
+
+    /// Как build, но запоминает, какая опция установила каждое поле.
+    fn configure(options: Vec<Opt<Self>>) -> Result<Configured<Self>, NonEmpty<OptionError>> {
+        let mut value = Self::defaults();
+        let mut errors: Vec<OptionError> = Vec::new();
+        let mut applied: Vec<Opt<Self>> = Vec::new();
+
+        for option in options {
+            if let Some(previous) = applied.iter().find(|p| p.field == option.field) {
+                errors.push(if previous.value == option.value {
+                    OptionError::Duplicate {
+                        option: option.to_string(),
+                        field: option.field,
+                    }
+                } else {
+                    OptionError::Conflict {
+                        field: option.field,
+                        first: previous.to_string(),
+                        second: option.to_string(),
+                    }
+                });
+                continue;
+            }
+            match option.apply(&mut value) {
+                Ok(()) => applied.push(option),
+                Err(message) => errors.push(OptionError::Invalid {
+                    option: option.to_string(),
+                    message,
+                }),
+            }
+        }
+
+        if errors.is_empty() {
+            if let Err(message) = value.validate() {
+                errors.push(OptionError::Rejected(message));
+            }
+        }
+        match NonEmpty::from_vec(errors) {
+            Some(errors) => Err(errors),
+            None => Ok(Configured::new(value, applied)),
+        }
+    }
+}
+
+/// Генерирует функции-опции для полей структуры:
+/// options! { House { pub fn floors(floors: i32) check |n: &i32| ...; } }.
+/// Функция принимает значение типа поля; необязательная проверка
+/// check получает значение и возвращает Result<(), String>.
+#[macro_export]
+macro_rules! options {
+    ($target:ty {
+        $(
+            $(#[$meta:meta])*
+            $vis:vis fn $name:ident($field:ident: $value:ty) $(check $check:expr)?;
+        )*
+    }) => {
+        $(
+            $(#[$meta])*
+            $vis fn $name(value: $value) -> $crate::functional::options::Opt<$target> {
+                $crate::functional::options::Opt::new(
+                    stringify!($name),
+                    stringify!($field),
+                    ::std::format!("{:?}", value),
+                    move |target: &mut $target| {
+                        $(($check)(&value)?;)?
+                        target.$field = ::std::clone::Clone::clone(&value);
+                        Ok(())
+                    },
+                    |target: &$target| ::std::format!("{:?}", target.$field),
+                )
+            }
+        )*
+    };
+}
+
+// HouseOption - функция для конфигурации дома.
+pub type HouseOption = Box<dyn Fn(&mut House)>;
+
+/// Структура дома.
+// #[derive(Debug, PartialEq)] - атрибут derive автоматически генерирует реализации трейтов Debug и PartialEq.
+// Debug позволяет выводить структуру в отладочном формате (println!("{:?}", house)).
+// PartialEq позволяет сравнивать структуры на равенство (house1 == house2).
+#[derive(Debug, PartialEq)]
+pub struct House {
+    pub material: String,
+    pub has_fireplace: bool,
+    pub floors: i32,
+}
+
+impl Options for House {
+    fn defaults() -> Self {
+        House {
+            material: DEFAULT_MATERIAL.to_string(),
+            has_fireplace: DEFAULT_HAS_FIREPLACE,
+            floors: DEFAULT_FLOORS,
+        }
+    }
+}
+
+impl House {
+    /// Создает новый дом с опциями.
+    pub fn new(options: Vec<HouseOption>) -> Self {
+        let mut house = House::defaults();
+
+        for option in options {
+            option(&mut house);
+        }
+
+        house
+    }
+
+    /// Создает дом из проверяемых опций или возвращает все ошибки опций.
+    pub fn try_new(options: Vec<Opt<House>>) -> Result<Self, NonEmpty<OptionError>> {
+        House::build(options)
+    }
+}
+
+options! {
+    House {
+        #[allow(dead_code)]
+        /// Опция для установки материала.
+        pub fn material(material: String) check |material: &String| {
+            if material.is_empty() {
+                Err("material must not be empty".to_string())
+            } else {
+                Ok(())
+            }
+        };
+
+        #[allow(dead_code)]
+        /// Опция для установки камина.
+        pub fn fireplace(has_fireplace: bool);
+
+        #[allow(dead_code)]
+        /// Опция для установки количества этажей.
+        pub fn floors(floors: i32) check |floors: &i32| {
+            if *floors < 1 {
+                Err(format!("floors must be positive, got {}", floors))
+            } else {
+                Ok(())
+            }
+        };
+    }
+}
+
+#[allow(dead_code)]
+/// Опция для установки материала кирпич.
+pub fn with_material_kerpic() -> HouseOption {
+    Box::new(|h: &mut House| {
+        h.material = "kerpic".to_string();
+    })
+}
+
+#[allow(dead_code)]
+/// Опция для отключения камина.
+pub fn without_fireplace() -> HouseOption {
+    Box::new(|h: &mut House| {
+        h.has_fireplace = false;
+    })
+}
+
+#[allow(dead_code)]
+/// Опция для установки количества этажей.
+pub fn with_floors(floors: i32) -> HouseOption {
+    Box::new(move |h: &mut House| {
+        h.floors = floors;
+    })
+}
+
+#[allow(dead_code)]
+/// Пресет одноэтажного кирпичного дома без камина.
+pub fn cottage() -> Preset<House> {
+    Preset::new(
+        "cottage",
+        vec![material("kerpic".to_string()), fireplace(false), floors(1)],
+    )
+}
+
+#[allow(dead_code)]
+/// Пресет большого каменного дома.
+pub fn manor() -> Preset<House> {
+    Preset::new("manor", vec![material("stone".to_string()), floors(4)])
+}
+
+#[test]
+fn test_option() {
+    // Дом по умолчанию
+    let default_house = House::new(vec![]);
+    assert_eq!(default_house.material, "wood");
+    assert_eq!(default_house.has_fireplace, true);
+    assert_eq!(default_house.floors, 2);
+
+    // Дом с кирпичом
+    let kerpic_house = House::new(vec![with_material_kerpic()]);
+    assert_eq!(kerpic_house.material, "kerpic");
+    assert_eq!(kerpic_house.has_fireplace, true);
+    assert_eq!(kerpic_house.floors, 2);
+
+    // Дом без камина и с 3 этажами
+    let custom_house = House::new(vec![without_fireplace(), with_floors(3)]);
+    assert_eq!(custom_house.material, "wood");
+    assert_eq!(custom_house.has_fireplace, false);
+    assert_eq!(custom_house.floors, 3);
+
+    // Дом со всеми опциями
+    let full_house = House::new(vec![
+        with_material_kerpic(),
+        without_fireplace(),
+        with_floors(5),
+    ]);
+    assert_eq!(full_house.material, "kerpic");
+    assert_eq!(full_house.has_fireplace, false);
+    assert_eq!(full_house.floors, 5);
+}
+
+#[test]
+fn test_validated_options() {
+    // Ошибки значений, повторы и противоречия собираются вместе
+    let errors = House::try_new(vec![
+        floors(-1),
+        material("kerpic".to_string()),
+        material("stone".to_string()),
+        fireplace(false),
+        fireplace(false),
+    ])
+    .unwrap_err()
+    .into_vec();
+    assert_eq!(
+        errors,
+        vec![
+            OptionError::Invalid {
+                option: "floors(-1)".to_string(),
+                message: "floors must be positive, got -1".to_string(),
+            },
+            OptionError::Conflict {
+                field: "material",
+                first: "material(\"kerpic\")".to_string(),
+                second: "material(\"stone\")".to_string(),
+            },
+            OptionError::Duplicate {
+                option: "fireplace(false)".to_string(),
+                field: "has_fireplace",
+            },
+        ]
+    );
+    assert_eq!(
+        errors[1].to_string(),
+        "material(\"stone\") conflicts with material(\"kerpic\") on field material"
+    );
+    assert_eq!(
+        House::try_new(vec![floors(4)]).unwrap(),
+        House::new(vec![with_floors(4)])
+    );
+
+    // Опция знает, какое поле и каким значением она устанавливает
+    let option = fireplace(true);
+    assert_eq!(
+        (option.name(), option.field(), option.value()),
+        ("fireplace", "has_fireplace", "true")
+    );
+
+    // House::new не проверяет опции: побеждает последняя
+    assert_eq!(House::new(vec![with_floors(3), with_floors(-1)]).floors, -1);
+
+    // Та же схема для конфигурации сервиса
+    #[derive(Debug)]
+    struct ServerConfig {
+        host: String,
+        port: u16,
+        workers: usize,
+        max_connections: usize,
+    }
+
+    impl Options for ServerConfig {
+        fn defaults() -> Self {
+            ServerConfig {
+                host: "localhost".to_string(),
+                port: 8080,
+                workers: 4,
+                max_connections: 100,
+            }
+        }
+
+        fn validate(&self) -> Result<(), String> {
+            if self.max_connections < self.workers {
+                return Err("max_connections must be at least workers".to_string());
+            }
+            Ok(())
+        }
+    }
+
+    options! {
+        ServerConfig {
+            fn host(host: String);
+            fn port(port: u16) check |port: &u16| {
+                if *port == 0 { Err("port must not be zero".to_string()) } else { Ok(()) }
+            };
+            fn workers(workers: usize);
+            fn max_connections(max_connections: usize);
+        }
+    }
+
+    let config =
+        ServerConfig::build(vec![host("0.0.0.0".to_string()), port(9000), workers(8)]).unwrap();
+    assert_eq!(
+        (config.host.as_str(), config.port, config.workers),
+        ("0.0.0.0", 9000, 8)
+    );
+    assert_eq!(config.max_connections, 100);
+
+    let errors = ServerConfig::build(vec![port(0)]).unwrap_err();
+    assert_eq!(errors.first().to_string(), "port(0): port must not be zero");
+    let errors = ServerConfig::build(vec![workers(8), max_connections(2)]).unwrap_err();
+    assert_eq!(
+        errors.into_vec(),
+        vec![OptionError::Rejected(
+            "max_connections must be at least workers".to_string()
+        )]
+    );
+}
@@ -0,0 +1,243 @@
+// Пресеты и объяснение опций.
+// Пресет - именованный набор опций, который можно переиспользовать, дополнять
+// и объединять с другими пресетами. Собранное через configure значение помнит,
+// какая опция (и из какого пресета) установила каждое поле, поэтому его можно
+// объяснить, сравнить со значениями по умолчанию и свести к минимальному списку опций.
+
+use std::fmt;
+
+use super::{Opt, Options};
+
+/// Именованный набор опций.
+pub struct Preset<T> {
+    name: String,
+    options: Vec<Opt<T>>,
+}
+
+impl<T> Clone for Preset<T> {
+    fn clone(&self) -> Self {
+        Preset {
+            name: self.name.clone(),
+            options: self.options.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for Preset<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Preset")
+            .field("name", &self.name)
+            .field("options", &self.options)
+            .finish()
+    }
+}
+
+impl<T> Preset<T> {
+    /// Создает пресет; каждая опция помечается его именем.
+    pub fn new(name: &str, options: Vec<Opt<T>>) -> Self {
+        Preset {
+            name: name.to_string(),
+            options: options
+                .into_iter()
+                .map(|option| option.in_preset(name))
+                .collect(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Добавляет опцию в пресет, заменяя опцию для того же поля.
+    pub fn with(mut self, option: Opt<T>) -> Self {
+        let option = option.in_preset(&self.name);
+        self.replace(option);
+        self
+    }
+
+    /// Объединяет пресеты: опции other заменяют опции self для тех же полей
+    /// и сохраняют имя своего пресета.
+    pub fn merge(mut self, other: Preset<T>) -> Self {
+        self.name = format!("{}+{}", self.name, other.name);
+        for option in other.options {
+            self.replace(option);
+        }
+        self
+    }
+
+    /// Опции пресета в порядке добавления.
+    pub fn options(&self) -> &[Opt<T>] {
+        &self.options
+    }
+
+    pub fn into_options(self) -> Vec<Opt<T>> {
+        self.options
+    }
+
+    fn replace(&mut self, option: Opt<T>) {
+        match self.options.iter_mut().find(|o| o.field == option.field) {
+            Some(existing) => *existing = option,
+            None => self.options.push(option),
+        }
+    }
+}
+
+/// Изменение поля относительно значения по умолчанию.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: &'static str,
+    /// Опция, установившая поле.
+    pub option: String,
+    pub default: String,
+    pub value: String,
+}
+
+impl fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} -> {} ({})",
+            self.field, self.default, self.value, self.option
+        )
+    }
+}
+
+/// Значение, собранное из опций, вместе с примененными опциями.
+pub struct Configured<T> {
+    value: T,
+    applied: Vec<Opt<T>>,
+}
+
+impl<T> Configured<T> {
+    pub(super) fn new(value: T, applied: Vec<Opt<T>>) -> Self {
+        Configured { value, applied }
+    }
+
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    pub fn into_value(self) -> T {
+        self.value
+    }
+
+    /// Примененные опции в порядке применения.
+    pub fn options(&self) -> &[Opt<T>] {
+        &self.applied
+    }
+
+    /// Опция, установившая поле, или None, если поле осталось по умолчанию.
+    pub fn set_by(&self, field: &str) -> Option<&Opt<T>> {
+        self.applied.iter().find(|option| option.field == field)
+    }
+
+    /// Объяснение: по строке на каждое установленное поле.
+    pub fn explain(&self) -> String {
+        self.applied
+            .iter()
+            .map(|option| {
+                let source = match option.preset() {
+                    Some(preset) => format!("{} from preset {}", option, preset),
+                    None => option.to_string(),
+                };
+                format!(
+                    "{} = {} <- {}",
+                    option.field,
+                    option.read(&self.value),
+                    source
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl<T: Options> Configured<T> {
+    /// Поля, значения которых отличаются от значений по умолчанию.
+    pub fn diff(&self) -> Vec<FieldChange> {
+        let defaults = T::defaults();
+        self.applied
+            .iter()
+            .map(|option| FieldChange {
+                field: option.field,
+                option: option.to_string(),
+                default: option.read(&defaults),
+                value: option.read(&self.value),
+            })
+            .filter(|change| change.default != change.value)
+            .collect()
+    }
+
+    /// Минимальный список опций, который собирает то же значение:
+    /// опции, оставившие поле по умолчанию, отбрасываются.
+    pub fn minimal_options(&self) -> Vec<Opt<T>> {
+        let defaults = T::defaults();
+        self.applied
+            .iter()
+            .filter(|option| option.read(&defaults) != option.read(&self.value))
+            .cloned()
+            .collect()
+    }
+}
+
+#[test]
+fn test_option_presets() {
+    use super::{cottage, fireplace, floors, manor, material, House};
+
+    // Пресет дополняется опцией для того же поля без конфликта
+    let preset = cottage().with(floors(2));
+    let fields: Vec<&str> = preset.options().iter().map(|o| o.field()).collect();
+    assert_eq!(fields, ["material", "has_fireplace", "floors"]);
+    let house = House::try_new(preset.into_options()).unwrap();
+    assert_eq!(
+        house,
+        House {
+            material: "kerpic".to_string(),
+            has_fireplace: false,
+            floors: 2,
+        }
+    );
+
+    // Объединение пресетов: опции второго пресета побеждают
+    let merged = cottage().merge(manor());
+    assert_eq!(merged.name(), "cottage+manor");
+    let configured = House::configure(merged.into_options()).unwrap();
+    assert_eq!(configured.value().material, "stone");
+    assert!(!configured.value().has_fireplace);
+    assert_eq!(configured.set_by("floors").unwrap().preset(), Some("manor"));
+    assert_eq!(
+        configured.set_by("has_fireplace").unwrap().preset(),
+        Some("cottage")
+    );
+    assert_eq!(
+        configured.explain(),
+        [
+            "material = \"stone\" <- material(\"stone\") from preset manor",
+            "has_fireplace = false <- fireplace(false) from preset cottage",
+            "floors = 4 <- floors(4) from preset manor",
+        ]
+        .join("\n")
+    );
+
+    // Сравнение со значениями по умолчанию пропускает опции, совпавшие с ними
+    let mut options = manor().into_options();
+    options.push(fireplace(true));
+    let configured = House::configure(options).unwrap();
+    assert_eq!(configured.options().len(), 3);
+    let diff = configured.diff();
+    assert_eq!(diff.len(), 2);
+    assert_eq!(diff[1].to_string(), "floors: 2 -> 4 (floors(4))");
+    assert!(configured.set_by("has_fireplace").is_some());
+
+    // Минимальный список опций собирает то же значение
+    let minimal = configured.minimal_options();
+    let names: Vec<String> = minimal.iter().map(ToString::to_string).collect();
+    assert_eq!(names, ["material(\"stone\")", "floors(4)"]);
+    assert_eq!(House::try_new(minimal).unwrap(), *configured.value());
+
+    // Дом по умолчанию объяснять нечего
+    let plain = House::configure(vec![material("wood".to_string())]).unwrap();
+    assert!(plain.diff().is_empty());
+    assert!(plain.minimal_options().is_empty());
+    assert_eq!(plain.set_by("floors").map(|o| o.name()), None);
+}
@@ -0,0 +1,193 @@
+// Персистентный односвязный список.
+// Добавление в начало и взятие хвоста - O(1): новый список разделяет узлы со старым,
+// поэтому старая версия остается доступной без копирования.
+
+use std::fmt;
+use std::rc::Rc;
+
+use crate::functional::functor::Functor;
+
+struct Node<T> {
+    value: T,
+    next: List<T>,
+}
+
+/// Неизменяемый список с разделяемым хвостом.
+pub struct List<T> {
+    head: Option<Rc<Node<T>>>,
+    len: usize,
+}
+
+impl<T> List<T> {
+    pub fn new() -> Self {
+        List { head: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Новый список с value в начале; текущий список становится его хвостом.
+    pub fn cons(&self, value: T) -> Self {
+        List {
+            head: Some(Rc::new(Node {
+                value,
+                next: self.clone(),
+            })),
+            len: self.len + 1,
+        }
+    }
+
+    pub fn head(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.value)
+    }
+
+    /// Список без первого элемента; для пустого списка - None.
+    pub fn tail(&self) -> Option<List<T>> {
+        self.head.as_ref().map(|node| node.next.clone())
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.head.as_deref(),
+        }
+    }
+
+    /// Разделяют ли два списка один и тот же первый узел.
+    pub fn ptr_eq(&self, other: &List<T>) -> bool {
+        match (&self.head, &other.head) {
+            (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl<T: Clone> List<T> {
+    pub fn reverse(&self) -> Self {
+        self.iter()
+            .fold(List::new(), |list, value| list.cons(value.clone()))
+    }
+}
+
+impl<T> Clone for List<T> {
+    fn clone(&self) -> Self {
+        List {
+            head: self.head.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<T> Default for List<T> {
+    fn default() -> Self {
+        List::new()
+    }
+}
+
+// Узлы освобождаются в цикле, а не рекурсивно, чтобы длинный список не переполнил стек.
+impl<T> Drop for List<T> {
+    fn drop(&mut self) {
+        let mut head = self.head.take();
+        while let Some(node) = head {
+            match Rc::try_unwrap(node) {
+                Ok(mut node) => head = node.next.head.take(),
+                Err(_) => break,
+            }
+        }
+    }
+}
+
+/// Итератор по элементам списка.
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.next.map(|node| {
+            self.next = node.next.head.as_deref();
+            &node.value
+        })
+    }
+}
+
+impl<'a, T> IntoIterator for &'a List<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+/// Собирает список в порядке итератора.
+impl<T> FromIterator<T> for List<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let values: Vec<T> = iter.into_iter().collect();
+        values
+            .into_iter()
+            .rev()
+            .fold(List::new(), |list, value| list.cons(value))
+    }
+}
+
+impl<T: PartialEq> PartialEq for List<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for List<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'f, T: Clone> Functor<'f, T> for List<T> {
+    type Output<U> = List<U>;
+
+    fn map<U, F>(self, f: F) -> List<U>
+    where
+        F: Fn(T) -> U + 'f,
+    {
+        self.iter().map(|value| f(value.clone())).collect()
+    }
+}
+
+#[test]
+fn test_persistent_list() {
+    let empty = List::new();
+    let one = empty.cons(1);
+    let two = one.cons(2);
+    let other = one.cons(20);
+
+    // Старые версии не меняются, хвост разделяется
+    assert!(empty.is_empty());
+    assert_eq!(one.iter().copied().collect::<Vec<_>>(), [1]);
+    assert_eq!(two.iter().copied().collect::<Vec<_>>(), [2, 1]);
+    assert_eq!(other.iter().copied().collect::<Vec<_>>(), [20, 1]);
+    assert!(two.tail().unwrap().ptr_eq(&other.tail().unwrap()));
+    assert_eq!(two.head(), Some(&2));
+    assert_eq!(empty.tail(), None);
+
+    let list: List<i32> = (1..=4).collect();
+    assert_eq!(list.len(), 4);
+    assert_eq!(list.reverse(), (1..=4).rev().collect());
+    assert_eq!(
+        list.clone().map(|x| x * 10),
+        List::from_iter([10, 20, 30, 40])
+    );
+    assert_eq!(format!("{:?}", list), "[1, 2, 3, 4]");
+
+    // Длинный список освобождается без переполнения стека
+    let long: List<u32> = (0..200_000).collect();
+    assert_eq!(long.len(), 200_000);
+    drop(long);
+}
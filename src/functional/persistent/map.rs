@@ -0,0 +1,353 @@
+// Персистентная хеш-таблица на префиксном дереве хешей (HAMT).
+// Хеш ключа разбивается на группы по 5 бит; узел-ветка хранит битовую карту занятых
+// позиций и только существующих потомков. Вставка и удаление копируют путь от корня
+// до листа, поэтому стоят O(log32 n) и не затрагивают старую версию.
+
+use std::fmt;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::rc::Rc;
+
+use crate::functional::functor::Functor;
+
+const BITS: u32 = 5;
+const MASK: u64 = (1 << BITS) - 1;
+
+enum Node<K, V> {
+    Branch {
+        bitmap: u32,
+        children: Vec<Rc<Node<K, V>>>,
+    },
+    /// Записи с одинаковым хешем.
+    Leaf { hash: u64, entries: Vec<(K, V)> },
+}
+
+/// Неизменяемое отображение со структурным разделением.
+pub struct Map<K, V> {
+    root: Rc<Node<K, V>>,
+    len: usize,
+}
+
+fn hash_of<K: Hash>(key: &K) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Позиция в ветке: номер бита и индекс потомка среди занятых позиций.
+fn slot(bitmap: u32, hash: u64, shift: u32) -> (u32, usize) {
+    let bit = 1u32 << ((hash >> shift) & MASK);
+    (bit, (bitmap & (bit - 1)).count_ones() as usize)
+}
+
+impl<K, V> Map<K, V> {
+    pub fn new() -> Self {
+        Map {
+            root: Rc::new(Node::Branch {
+                bitmap: 0,
+                children: Vec::new(),
+            }),
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Записи в порядке обхода дерева (порядок хешей, а не вставки).
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> + '_ {
+        let mut entries = Vec::with_capacity(self.len);
+        let mut stack = vec![self.root.as_ref()];
+        while let Some(node) = stack.pop() {
+            match node {
+                Node::Branch { children, .. } => {
+                    stack.extend(children.iter().rev().map(|child| child.as_ref()))
+                }
+                Node::Leaf { entries: leaf, .. } => {
+                    entries.extend(leaf.iter().map(|(key, value)| (key, value)))
+                }
+            }
+        }
+        entries.into_iter()
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> + '_ {
+        self.iter().map(|(key, _)| key)
+    }
+}
+
+impl<K: Hash + Eq, V> Map<K, V> {
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let hash = hash_of(key);
+        let mut node = self.root.as_ref();
+        let mut shift = 0;
+        loop {
+            match node {
+                Node::Branch { bitmap, children } => {
+                    let (bit, index) = slot(*bitmap, hash, shift);
+                    if bitmap & bit == 0 {
+                        return None;
+                    }
+                    node = &children[index];
+                    shift += BITS;
+                }
+                Node::Leaf { entries, .. } => {
+                    return entries.iter().find(|(k, _)| k == key).map(|(_, v)| v);
+                }
+            }
+        }
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Map<K, V> {
+    /// Новое отображение с записью key -> value (заменяет прежнее значение).
+    pub fn insert(&self, key: K, value: V) -> Self {
+        let hash = hash_of(&key);
+        let (root, added) = Map::insert_in(&self.root, 0, hash, key, value);
+        Map {
+            root,
+            len: self.len + usize::from(added),
+        }
+    }
+
+    /// Новое отображение без key; если ключа нет, разделяет все узлы с текущим.
+    pub fn remove(&self, key: &K) -> Self {
+        let hash = hash_of(key);
+        match Map::remove_in(&self.root, 0, hash, key) {
+            Some(root) => Map {
+                root: root.unwrap_or_else(|| Map::new().root),
+                len: self.len - 1,
+            },
+            None => self.clone(),
+        }
+    }
+
+    fn insert_in(
+        node: &Rc<Node<K, V>>,
+        shift: u32,
+        hash: u64,
+        key: K,
+        value: V,
+    ) -> (Rc<Node<K, V>>, bool) {
+        match node.as_ref() {
+            Node::Branch { bitmap, children } => {
+                let (bit, index) = slot(*bitmap, hash, shift);
+                let mut children = children.clone();
+                let added = if bitmap & bit == 0 {
+                    let entries = vec![(key, value)];
+                    children.insert(index, Rc::new(Node::Leaf { hash, entries }));
+                    true
+                } else {
+                    let (child, added) =
+                        Map::insert_in(&children[index], shift + BITS, hash, key, value);
+                    children[index] = child;
+                    added
+                };
+                let bitmap = bitmap | bit;
+                (Rc::new(Node::Branch { bitmap, children }), added)
+            }
+            Node::Leaf {
+                hash: leaf_hash,
+                entries,
+            } if *leaf_hash == hash => {
+                let mut entries = entries.clone();
+                let added = match entries.iter_mut().find(|(k, _)| *k == key) {
+                    Some(entry) => {
+                        entry.1 = value;
+                        false
+                    }
+                    None => {
+                        entries.push((key, value));
+                        true
+                    }
+                };
+                (Rc::new(Node::Leaf { hash, entries }), added)
+            }
+            Node::Leaf {
+                hash: leaf_hash, ..
+            } => {
+                // Хеши различаются: лист уходит на уровень ниже, в новую ветку
+                let (bit, _) = slot(0, *leaf_hash, shift);
+                let branch = Rc::new(Node::Branch {
+                    bitmap: bit,
+                    children: vec![Rc::clone(node)],
+                });
+                Map::insert_in(&branch, shift, hash, key, value)
+            }
+        }
+    }
+
+    /// None - ключ не найден; Some(None) - узел опустел и удаляется.
+    fn remove_in(
+        node: &Rc<Node<K, V>>,
+        shift: u32,
+        hash: u64,
+        key: &K,
+    ) -> Option<Option<Rc<Node<K, V>>>> {
+        match node.as_ref() {
+            Node::Branch { bitmap, children } => {
+                let (bit, index) = slot(*bitmap, hash, shift);
+                if bitmap & bit == 0 {
+                    return None;
+                }
+                let child = Map::remove_in(&children[index], shift + BITS, hash, key)?;
+                let mut children = children.clone();
+                let mut bitmap = *bitmap;
+                match child {
+                    Some(child) => children[index] = child,
+                    None => {
+                        children.remove(index);
+                        bitmap &= !bit;
+                    }
+                }
+                if children.is_empty() {
+                    return Some(None);
+                }
+                Some(Some(Rc::new(Node::Branch { bitmap, children })))
+            }
+            Node::Leaf {
+                hash: leaf_hash,
+                entries,
+            } => {
+                if *leaf_hash != hash {
+                    return None;
+                }
+                let position = entries.iter().position(|(k, _)| k == key)?;
+                let mut entries = entries.clone();
+                entries.remove(position);
+                if entries.is_empty() {
+                    return Some(None);
+                }
+                Some(Some(Rc::new(Node::Leaf { hash, entries })))
+            }
+        }
+    }
+}
+
+impl<K, V> Clone for Map<K, V> {
+    fn clone(&self) -> Self {
+        Map {
+            root: Rc::clone(&self.root),
+            len: self.len,
+        }
+    }
+}
+
+impl<K, V> Default for Map<K, V> {
+    fn default() -> Self {
+        Map::new()
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> FromIterator<(K, V)> for Map<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        iter.into_iter()
+            .fold(Map::new(), |map, (key, value)| map.insert(key, value))
+    }
+}
+
+impl<K: Hash + Eq, V: PartialEq> PartialEq for Map<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len
+            && self
+                .iter()
+                .all(|(key, value)| other.get(key) == Some(value))
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for Map<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// Функтор по значениям: ключи и форма дерева сохраняются.
+impl<'f, K: Clone, V: Clone> Functor<'f, V> for Map<K, V> {
+    type Output<U> = Map<K, U>;
+
+    fn map<U, F>(self, f: F) -> Map<K, U>
+    where
+        F: Fn(V) -> U + 'f,
+    {
+        fn map_node<K: Clone, V: Clone, U>(
+            node: &Node<K, V>,
+            f: &dyn Fn(V) -> U,
+        ) -> Rc<Node<K, U>> {
+            Rc::new(match node {
+                Node::Branch { bitmap, children } => Node::Branch {
+                    bitmap: *bitmap,
+                    children: children.iter().map(|child| map_node(child, f)).collect(),
+                },
+                Node::Leaf { hash, entries } => Node::Leaf {
+                    hash: *hash,
+                    entries: entries
+                        .iter()
+                        .map(|(key, value)| (key.clone(), f(value.clone())))
+                        .collect(),
+                },
+            })
+        }
+        Map {
+            root: map_node(&self.root, &f),
+            len: self.len,
+        }
+    }
+}
+
+#[test]
+fn test_persistent_map() {
+    let empty: Map<String, i32> = Map::new();
+    let one = empty.insert("one".to_string(), 1);
+    let two = one.insert("two".to_string(), 2);
+    assert_eq!((empty.len(), one.len(), two.len()), (0, 1, 2));
+    assert_eq!(two.get(&"one".to_string()), Some(&1));
+    assert_eq!(one.get(&"two".to_string()), None);
+
+    // Замена значения не меняет размер и старую версию
+    let replaced = two.insert("one".to_string(), 10);
+    assert_eq!(replaced.len(), 2);
+    let mut keys: Vec<&String> = replaced.keys().collect();
+    keys.sort();
+    assert_eq!(keys, ["one", "two"]);
+    assert_eq!(replaced.get(&"one".to_string()), Some(&10));
+    assert_eq!(two.get(&"one".to_string()), Some(&1));
+
+    // Много ключей, удаление и сравнение
+    let squares: Map<u32, u32> = (0..2000).map(|n| (n, n * n)).collect();
+    assert_eq!(squares.len(), 2000);
+    assert!((0..2000).all(|n| squares.get(&n) == Some(&(n * n))));
+    let fewer = (0..2000)
+        .filter(|n| n % 2 == 0)
+        .fold(squares.clone(), |map, n| map.remove(&n));
+    assert_eq!(fewer.len(), 1000);
+    assert!(!fewer.contains_key(&10));
+    assert!(fewer.contains_key(&11));
+    assert!(squares.contains_key(&10));
+    assert_eq!(fewer.remove(&10).len(), 1000);
+    let rebuilt: Map<u32, u32> = (0..2000)
+        .filter(|n| n % 2 == 1)
+        .map(|n| (n, n * n))
+        .collect();
+    assert_eq!(fewer, rebuilt);
+    let cleared = (0..2000).fold(squares.clone(), |map, n| map.remove(&n));
+    assert!(cleared.is_empty());
+    assert_eq!(cleared, Map::new());
+
+    // Функтор меняет значения, сохраняя ключи
+    let halves = fewer.map(|square| square / 2);
+    assert_eq!(halves.get(&11), Some(&60));
+    assert_eq!(halves.len(), 1000);
+    let labels = two.map(|n| format!("#{}", n));
+    assert_eq!(
+        labels.get(&"two".to_string()).map(String::as_str),
+        Some("#2")
+    );
+}
@@ -0,0 +1,45 @@
+// Функциональный паттерн Persistent collections: неизменяемые коллекции со структурным
+// разделением. Каждое изменение возвращает новую версию, которая разделяет с прежней
+// все неизмененные узлы, поэтому снимок состояния - это клонирование указателя,
+// а изменение копирует O(log n) узлов вместо всей коллекции.
+// List - односвязный список, Vector - префиксное дерево с ветвлением 32,
+// Map - префиксное дерево хешей (HAMT).
+// Пример: история правок документа с отменой, где каждый снимок дешевый.
+
+pub mod list;
+pub mod map;
+pub mod vector;
+
+#[test]
+fn test_persistent_history() {
+    use list::List;
+    use map::Map;
+    use vector::Vector;
+
+    // Документ - вектор строк, история - список снимков
+    let mut document: Vector<String> = (1..=100).map(|n| format!("line {}", n)).collect();
+    let mut history: List<Vector<String>> = List::new();
+
+    for edit in 0..10 {
+        history = history.cons(document.clone());
+        document = document.set(edit * 10, format!("edited {}", edit)).unwrap();
+    }
+    assert_eq!(history.len(), 10);
+    assert_eq!(document.get(90).unwrap(), "edited 9");
+
+    // Отмена трех правок
+    for _ in 0..3 {
+        document = history.head().unwrap().clone();
+        history = history.tail().unwrap();
+    }
+    assert_eq!(document.get(60).unwrap(), "edited 6");
+    assert_eq!(document.get(70).unwrap(), "line 71");
+    assert_eq!(history.len(), 7);
+
+    // Счетчики по словам - отображение, которое тоже хранит версии
+    let counts: Map<&str, usize> = Map::new().insert("line", 93).insert("edited", 7);
+    let before = counts.clone();
+    let counts = counts.insert("line", 92).insert("edited", 8);
+    assert_eq!(before.get(&"edited"), Some(&7));
+    assert_eq!(counts.get(&"edited"), Some(&8));
+}
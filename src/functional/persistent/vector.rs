@@ -0,0 +1,278 @@
+// Персистентный вектор на префиксном дереве с ветвлением 32.
+// Индекс разбивается на группы по 5 бит, каждая выбирает потомка на своем уровне.
+// Чтение, замена и добавление в конец - O(log32 n): изменение копирует только путь
+// от корня до листа, остальные узлы разделяются со старой версией.
+
+use std::fmt;
+use std::rc::Rc;
+
+use crate::functional::functor::Functor;
+
+const BITS: u32 = 5;
+const WIDTH: usize = 1 << BITS;
+const MASK: usize = WIDTH - 1;
+
+enum Node<T> {
+    Branch(Vec<Rc<Node<T>>>),
+    Leaf(Vec<T>),
+}
+
+/// Неизменяемый вектор со структурным разделением.
+pub struct Vector<T> {
+    root: Rc<Node<T>>,
+    len: usize,
+    /// Сдвиг индекса для корня: BITS * (высота дерева - 1).
+    shift: u32,
+}
+
+impl<T> Vector<T> {
+    pub fn new() -> Self {
+        Vector {
+            root: Rc::new(Node::Leaf(Vec::new())),
+            len: 0,
+            shift: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len {
+            return None;
+        }
+        let mut node = self.root.as_ref();
+        let mut level = self.shift;
+        loop {
+            match node {
+                Node::Branch(children) => {
+                    node = &children[(index >> level) & MASK];
+                    level -= BITS;
+                }
+                Node::Leaf(values) => return Some(&values[index & MASK]),
+            }
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        (0..self.len).filter_map(move |index| self.get(index))
+    }
+
+    /// Разделяют ли два вектора один и тот же корень.
+    pub fn ptr_eq(&self, other: &Vector<T>) -> bool {
+        Rc::ptr_eq(&self.root, &other.root)
+    }
+
+    fn path(level: u32, value: T) -> Rc<Node<T>> {
+        if level == 0 {
+            Rc::new(Node::Leaf(vec![value]))
+        } else {
+            Rc::new(Node::Branch(vec![Vector::path(level - BITS, value)]))
+        }
+    }
+}
+
+impl<T: Clone> Vector<T> {
+    /// Новый вектор с value в конце.
+    pub fn push_back(&self, value: T) -> Self {
+        let capacity = 1usize << (self.shift + BITS);
+        if self.len == capacity {
+            // Дерево заполнено: старый корень становится первым потомком нового
+            let root = Node::Branch(vec![Rc::clone(&self.root), Vector::path(self.shift, value)]);
+            return Vector {
+                root: Rc::new(root),
+                len: self.len + 1,
+                shift: self.shift + BITS,
+            };
+        }
+        Vector {
+            root: Vector::push_in(&self.root, self.shift, self.len, value),
+            len: self.len + 1,
+            shift: self.shift,
+        }
+    }
+
+    /// Новый вектор с замененным элементом; для индекса за границей - None.
+    pub fn set(&self, index: usize, value: T) -> Option<Self> {
+        if index >= self.len {
+            return None;
+        }
+        Some(Vector {
+            root: Vector::set_in(&self.root, self.shift, index, value),
+            len: self.len,
+            shift: self.shift,
+        })
+    }
+
+    /// Новый вектор, в котором к элементу применена функция.
+    pub fn update<F>(&self, index: usize, f: F) -> Option<Self>
+    where
+        F: FnOnce(&T) -> T,
+    {
+        let value = f(self.get(index)?);
+        self.set(index, value)
+    }
+
+    fn push_in(node: &Node<T>, level: u32, index: usize, value: T) -> Rc<Node<T>> {
+        match node {
+            Node::Leaf(values) => {
+                let mut values = values.clone();
+                values.push(value);
+                Rc::new(Node::Leaf(values))
+            }
+            Node::Branch(children) => {
+                let mut children = children.clone();
+                let slot = (index >> level) & MASK;
+                if slot < children.len() {
+                    children[slot] = Vector::push_in(&children[slot], level - BITS, index, value);
+                } else {
+                    children.push(Vector::path(level - BITS, value));
+                }
+                Rc::new(Node::Branch(children))
+            }
+        }
+    }
+
+    fn set_in(node: &Node<T>, level: u32, index: usize, value: T) -> Rc<Node<T>> {
+        match node {
+            Node::Leaf(values) => {
+                let mut values = values.clone();
+                values[index & MASK] = value;
+                Rc::new(Node::Leaf(values))
+            }
+            Node::Branch(children) => {
+                let mut children = children.clone();
+                let slot = (index >> level) & MASK;
+                children[slot] = Vector::set_in(&children[slot], level - BITS, index, value);
+                Rc::new(Node::Branch(children))
+            }
+        }
+    }
+}
+
+impl<T> Clone for Vector<T> {
+    fn clone(&self) -> Self {
+        Vector {
+            root: Rc::clone(&self.root),
+            len: self.len,
+            shift: self.shift,
+        }
+    }
+}
+
+impl<T> Default for Vector<T> {
+    fn default() -> Self {
+        Vector::new()
+    }
+}
+
+impl<T: Clone> FromIterator<T> for Vector<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        iter.into_iter()
+            .fold(Vector::new(), |vector, value| vector.push_back(value))
+    }
+}
+
+impl<T: PartialEq> PartialEq for Vector<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Vector<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'f, T: Clone> Functor<'f, T> for Vector<T> {
+    type Output<U> = Vector<U>;
+
+    fn map<U, F>(self, f: F) -> Vector<U>
+    where
+        F: Fn(T) -> U + 'f,
+    {
+        let mut mapped = Vec::with_capacity(self.len);
+        mapped.extend(self.iter().map(|value| f(value.clone())));
+        // Vector<U> собирается без требования U: Clone
+        let mut result = Vector::new();
+        for value in mapped {
+            result = result.push_owned(value);
+        }
+        result
+    }
+}
+
+impl<T> Vector<T> {
+    /// Добавление в конец без копирования листьев: только для вектора,
+    /// который ни с кем не разделяет узлы (при построении).
+    fn push_owned(mut self, value: T) -> Self {
+        let capacity = 1usize << (self.shift + BITS);
+        if self.len == capacity {
+            let root = Node::Branch(vec![self.root, Vector::path(self.shift, value)]);
+            return Vector {
+                root: Rc::new(root),
+                len: self.len + 1,
+                shift: self.shift + BITS,
+            };
+        }
+        let mut node = Rc::get_mut(&mut self.root).expect("vector under construction is shared");
+        let mut level = self.shift;
+        loop {
+            match node {
+                Node::Leaf(values) => {
+                    values.push(value);
+                    break;
+                }
+                Node::Branch(children) => {
+                    let slot = (self.len >> level) & MASK;
+                    if slot == children.len() {
+                        children.push(Vector::path(level - BITS, value));
+                        break;
+                    }
+                    node = Rc::get_mut(&mut children[slot])
+                        .expect("vector under construction is shared");
+                    level -= BITS;
+                }
+            }
+        }
+        self.len += 1;
+        self
+    }
+}
+
+#[test]
+fn test_persistent_vector() {
+    let numbers: Vector<usize> = (0..1000).collect();
+    assert_eq!(numbers.len(), 1000);
+    assert!(!numbers.is_empty() && Vector::<usize>::new().is_empty());
+    assert_eq!(numbers.get(0), Some(&0));
+    assert_eq!(numbers.get(999), Some(&999));
+    assert_eq!(numbers.get(1000), None);
+    assert!(numbers.iter().copied().eq(0..1000));
+
+    // Изменение не затрагивает старую версию
+    let changed = numbers.set(500, 0).unwrap();
+    assert_eq!(changed.get(500), Some(&0));
+    assert_eq!(numbers.get(500), Some(&500));
+    assert_eq!(numbers.set(1000, 0), None);
+    let bumped = changed.update(1, |x| x + 100).unwrap();
+    assert_eq!(bumped.get(1), Some(&101));
+    let longer = numbers.push_back(1000);
+    assert_eq!((numbers.len(), longer.len()), (1000, 1001));
+    assert_eq!(longer.get(1000), Some(&1000));
+    assert!(numbers.clone().ptr_eq(&numbers));
+
+    // Функтор
+    let words: Vector<usize> = Vector::from_iter([1, 2, 3]);
+    let strings = words.map(|n| "x".repeat(n));
+    assert_eq!(format!("{:?}", strings), "[\"x\", \"xx\", \"xxx\"]");
+    let big = numbers.map(|n| n * 2);
+    assert_eq!(big.get(999), Some(&1998));
+    assert_eq!(big.len(), 1000);
+}
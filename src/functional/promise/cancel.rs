@@ -0,0 +1,93 @@
+// Кооперативная отмена Promise.
+// CancellationToken разделяется между вызывающим кодом и операциями;
+// отмена токена отклоняет все привязанные к нему Promise ошибкой Cancelled.
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use super::Promise;
+
+/// Ошибка отмены операции.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "operation cancelled")
+    }
+}
+
+#[derive(Default)]
+struct TokenState {
+    cancelled: bool,
+    callbacks: Vec<Box<dyn FnOnce() + Send>>,
+}
+
+/// Токен отмены. Клоны разделяют одно состояние.
+#[derive(Clone, Default)]
+pub struct CancellationToken {
+    state: Arc<Mutex<TokenState>>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        CancellationToken::default()
+    }
+
+    /// Отменяет токен и вызывает зарегистрированные обработчики. Повторная отмена ничего не делает.
+    pub fn cancel(&self) {
+        let callbacks = {
+            let mut state = self.state.lock().unwrap();
+            if state.cancelled {
+                return;
+            }
+            state.cancelled = true;
+            std::mem::take(&mut state.callbacks)
+        };
+        for callback in callbacks {
+            callback();
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.state.lock().unwrap().cancelled
+    }
+
+    /// Регистрирует обработчик отмены. Если токен уже отменен, обработчик вызывается сразу.
+    pub fn on_cancel<F>(&self, callback: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let mut state = self.state.lock().unwrap();
+        if state.cancelled {
+            drop(state);
+            callback();
+        } else {
+            state.callbacks.push(Box::new(callback));
+        }
+    }
+}
+
+impl<T, E> Promise<T, E>
+where
+    T: Clone + Send + 'static,
+    E: Clone + Send + 'static,
+{
+    /// Возвращает Promise, который отклоняется ошибкой Cancelled при отмене токена,
+    /// если исходный Promise к тому моменту еще не завершен.
+    pub fn with_cancellation(self, token: &CancellationToken) -> Promise<T, E>
+    where
+        E: From<Cancelled>,
+    {
+        let next = Promise::with_spawner(self.spawner());
+        let target = next.clone();
+        self.subscribe(move |result| {
+            target.settle(result);
+        });
+        let target = next.clone();
+        token.on_cancel(move || {
+            target.settle(Err(Cancelled.into()));
+        });
+        next
+    }
+}
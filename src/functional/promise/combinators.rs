@@ -0,0 +1,219 @@
+// Комбинаторы над наборами Promise с семантикой JavaScript:
+// all - все значения или первая ошибка, race - первый завершившийся,
+// any - первое значение или все ошибки, all_settled - все результаты без отклонения.
+
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+
+use super::Promise;
+
+/// Накопитель результатов: по слоту на каждый входной Promise и счетчик оставшихся.
+struct Slots<V> {
+    values: Vec<Option<V>>,
+    remaining: usize,
+}
+
+impl<V> Slots<V> {
+    fn new(len: usize) -> Arc<Mutex<Self>> {
+        Arc::new(Mutex::new(Slots {
+            values: (0..len).map(|_| None).collect(),
+            remaining: len,
+        }))
+    }
+
+    /// Сохраняет значение и возвращает все значения, если это был последний слот.
+    fn fill(&mut self, index: usize, value: V) -> Option<Vec<V>> {
+        self.values[index] = Some(value);
+        self.remaining -= 1;
+        if self.remaining == 0 {
+            Some(self.values.drain(..).map(Option::unwrap).collect())
+        } else {
+            None
+        }
+    }
+}
+
+impl<T, E> Promise<T, E>
+where
+    T: Clone + Send + 'static,
+    E: Clone + Send + 'static,
+{
+    /// Разрешается вектором значений в исходном порядке, когда разрешены все Promise.
+    /// Отклоняется первой же ошибкой, не дожидаясь остальных.
+    pub fn all<I>(promises: I) -> Promise<Vec<T>, E>
+    where
+        I: IntoIterator<Item = Promise<T, E>>,
+    {
+        let promises: Vec<_> = promises.into_iter().collect();
+        let output = Promise::with_spawner(first_spawner(&promises));
+        if promises.is_empty() {
+            output.settle(Ok(Vec::new()));
+            return output;
+        }
+
+        let slots = Slots::new(promises.len());
+        for (index, promise) in promises.into_iter().enumerate() {
+            let output = output.clone();
+            let slots = Arc::clone(&slots);
+            promise.subscribe(move |result| match result {
+                Ok(value) => {
+                    let done = slots.lock().unwrap().fill(index, value);
+                    if let Some(values) = done {
+                        output.settle(Ok(values));
+                    }
+                }
+                Err(error) => {
+                    output.settle(Err(error));
+                }
+            });
+        }
+        output
+    }
+
+    /// Завершается так же, как первый завершившийся Promise.
+    /// Для пустого набора остается в состоянии Pending навсегда.
+    pub fn race<I>(promises: I) -> Promise<T, E>
+    where
+        I: IntoIterator<Item = Promise<T, E>>,
+    {
+        let promises: Vec<_> = promises.into_iter().collect();
+        let output = Promise::with_spawner(first_spawner(&promises));
+        for promise in promises {
+            let output = output.clone();
+            promise.subscribe(move |result| {
+                output.settle(result);
+            });
+        }
+        output
+    }
+
+    /// Разрешается первым успешным значением.
+    /// Отклоняется вектором всех ошибок (в исходном порядке), если отклонены все Promise.
+    pub fn any<I>(promises: I) -> Promise<T, Vec<E>>
+    where
+        I: IntoIterator<Item = Promise<T, E>>,
+    {
+        let promises: Vec<_> = promises.into_iter().collect();
+        let output = Promise::with_spawner(first_spawner(&promises));
+        if promises.is_empty() {
+            output.settle(Err(Vec::new()));
+            return output;
+        }
+
+        let slots = Slots::new(promises.len());
+        for (index, promise) in promises.into_iter().enumerate() {
+            let output = output.clone();
+            let slots = Arc::clone(&slots);
+            promise.subscribe(move |result| match result {
+                Ok(value) => {
+                    output.settle(Ok(value));
+                }
+                Err(error) => {
+                    let done = slots.lock().unwrap().fill(index, error);
+                    if let Some(errors) = done {
+                        output.settle(Err(errors));
+                    }
+                }
+            });
+        }
+        output
+    }
+
+    /// Разрешается вектором результатов, когда завершены все Promise. Никогда не отклоняется.
+    pub fn all_settled<I>(promises: I) -> Promise<Vec<Result<T, E>>, Infallible>
+    where
+        I: IntoIterator<Item = Promise<T, E>>,
+    {
+        let promises: Vec<_> = promises.into_iter().collect();
+        let output = Promise::with_spawner(first_spawner(&promises));
+        if promises.is_empty() {
+            output.settle(Ok(Vec::new()));
+            return output;
+        }
+
+        let slots = Slots::new(promises.len());
+        for (index, promise) in promises.into_iter().enumerate() {
+            let output = output.clone();
+            let slots = Arc::clone(&slots);
+            promise.subscribe(move |result| {
+                let done = slots.lock().unwrap().fill(index, result);
+                if let Some(results) = done {
+                    output.settle(Ok(results));
+                }
+            });
+        }
+        output
+    }
+}
+
+/// Результирующий Promise наследует исполнитель первого входного Promise.
+fn first_spawner<T, E>(promises: &[Promise<T, E>]) -> Option<super::executor::Spawner>
+where
+    T: Clone + Send + 'static,
+    E: Clone + Send + 'static,
+{
+    promises.first().and_then(Promise::spawner)
+}
+
+#[test]
+fn test_promise_combinators() {
+    // all: все разрешены, порядок сохраняется независимо от порядка завершения
+    let (first, first_resolver) = Promise::<i32, &str>::pending();
+    let (second, second_resolver) = Promise::<i32, &str>::pending();
+    let all = Promise::all(vec![first, second, Promise::fulfilled(3)]);
+    second_resolver.resolve(2);
+    assert!(all.result().is_none());
+    first_resolver.resolve(1);
+    assert_eq!(all.result(), Some(Ok(vec![1, 2, 3])));
+
+    // all: отклоняется первой ошибкой, не дожидаясь ожидающих
+    let (pending, _resolver) = Promise::<i32, &str>::pending();
+    let all = Promise::all(vec![
+        pending,
+        Promise::rejected("bad"),
+        Promise::rejected("worse"),
+    ]);
+    assert_eq!(all.result(), Some(Err("bad")));
+
+    let empty = Promise::<i32, &str>::all(Vec::new());
+    assert_eq!(empty.result(), Some(Ok(vec![])));
+
+    // race: побеждает первый завершившийся, в том числе с ошибкой
+    let (slow, slow_resolver) = Promise::<i32, &str>::pending();
+    let (fast, fast_resolver) = Promise::<i32, &str>::pending();
+    let race = Promise::race(vec![slow, fast]);
+    fast_resolver.reject("fast failure");
+    slow_resolver.resolve(1);
+    assert_eq!(race.result(), Some(Err("fast failure")));
+
+    let never = Promise::<i32, &str>::race(Vec::new());
+    assert!(never.is_pending());
+
+    // any: первое успешное значение, ошибки пропускаются
+    let (pending, resolver) = Promise::<i32, &str>::pending();
+    let any = Promise::any(vec![
+        Promise::rejected("a"),
+        pending,
+        Promise::rejected("b"),
+    ]);
+    assert!(any.is_pending());
+    resolver.resolve(7);
+    assert_eq!(any.result(), Some(Ok(7)));
+
+    // any: все отклонены - все ошибки в исходном порядке
+    let (pending, resolver) = Promise::<i32, &str>::pending();
+    let any = Promise::any(vec![pending, Promise::rejected("b")]);
+    resolver.reject("a");
+    assert_eq!(any.result(), Some(Err(vec!["a", "b"])));
+
+    let empty = Promise::<i32, &str>::any(Vec::new());
+    assert_eq!(empty.result(), Some(Err(vec![])));
+
+    // all_settled: ждет всех и собирает и значения, и ошибки
+    let (pending, resolver) = Promise::<i32, &str>::pending();
+    let settled =
+        Promise::all_settled(vec![Promise::fulfilled(1), Promise::rejected("x"), pending]);
+    assert!(settled.is_pending());
+    resolver.resolve(3);
+    assert_eq!(settled.result(), Some(Ok(vec![Ok(1), Err("x"), Ok(3)])));
+}
@@ -0,0 +1,107 @@
+// Однопоточный исполнитель для Promise.
+// Продолжения (then/catch), зарегистрированные на Promise, привязанном к исполнителю,
+// не вызываются в потоке, который разрешил Promise, а ставятся в очередь задач.
+// Очередь разбирается в одном потоке - том, который вызывает run_until_stalled или block_on.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Condvar, Mutex};
+
+use super::{Promise, Resolver};
+
+/// Задача исполнителя.
+type Task = Box<dyn FnOnce() + Send>;
+
+/// Общая очередь задач исполнителя и его Spawner-ов.
+struct TaskQueue {
+    tasks: Mutex<VecDeque<Task>>,
+    ready: Condvar,
+}
+
+/// Однопоточный исполнитель задач.
+pub struct Executor {
+    queue: Arc<TaskQueue>,
+}
+
+/// Дескриптор для постановки задач в очередь исполнителя.
+/// Можно клонировать и передавать в другие потоки.
+#[derive(Clone)]
+pub struct Spawner {
+    queue: Arc<TaskQueue>,
+}
+
+impl Spawner {
+    /// Ставит задачу в очередь исполнителя.
+    pub fn spawn<F>(&self, task: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.queue.tasks.lock().unwrap().push_back(Box::new(task));
+        self.queue.ready.notify_one();
+    }
+}
+
+impl Executor {
+    pub fn new() -> Self {
+        Executor {
+            queue: Arc::new(TaskQueue {
+                tasks: Mutex::new(VecDeque::new()),
+                ready: Condvar::new(),
+            }),
+        }
+    }
+
+    /// Возвращает дескриптор для постановки задач в очередь.
+    pub fn spawner(&self) -> Spawner {
+        Spawner {
+            queue: Arc::clone(&self.queue),
+        }
+    }
+
+    /// Создает ожидающий Promise, продолжения которого выполняются этим исполнителем.
+    pub fn promise<T, E>(&self) -> (Promise<T, E>, Resolver<T, E>)
+    where
+        T: Clone + Send + 'static,
+        E: Clone + Send + 'static,
+    {
+        Promise::pending_on(self.spawner())
+    }
+
+    /// Выполняет задачи, пока очередь не опустеет. Возвращает число выполненных задач.
+    pub fn run_until_stalled(&self) -> usize {
+        let mut executed = 0;
+        while let Some(task) = self.next_task() {
+            task();
+            executed += 1;
+        }
+        executed
+    }
+
+    /// Выполняет задачи до тех пор, пока Promise не завершится, и возвращает его результат.
+    /// Если очередь пуста, поток засыпает до появления новой задачи.
+    pub fn block_on<T, E>(&self, promise: &Promise<T, E>) -> Result<T, E>
+    where
+        T: Clone + Send + 'static,
+        E: Clone + Send + 'static,
+    {
+        // Завершение Promise ставит в очередь пустую задачу, чтобы разбудить исполнитель.
+        let spawner = self.spawner();
+        promise.subscribe(move |_| spawner.spawn(|| {}));
+
+        loop {
+            if let Some(result) = promise.result() {
+                return result;
+            }
+            let mut tasks = self.queue.tasks.lock().unwrap();
+            while tasks.is_empty() {
+                tasks = self.queue.ready.wait(tasks).unwrap();
+            }
+            let task = tasks.pop_front().unwrap();
+            drop(tasks);
+            task();
+        }
+    }
+
+    fn next_task(&self) -> Option<Task> {
+        self.queue.tasks.lock().unwrap().pop_front()
+    }
+}
@@ -0,0 +1,152 @@
+// Мост между Promise и std::future::Future.
+// Promise можно ожидать через .await внутри async-кода, а любой Future можно
+// превратить в Promise, который опрашивается исполнителем Executor.
+// Функция block_on позволяет дождаться Future без внешнего рантайма.
+
+use std::future::Future;
+use std::pin::{pin, Pin};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+
+use super::executor::Spawner;
+use super::{Promise, PromiseState};
+
+/// Future, упакованный для хранения в задаче исполнителя.
+type BoxFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+impl<T, E> Future for Promise<T, E>
+where
+    T: Clone + Send + 'static,
+    E: Clone + Send + 'static,
+{
+    type Output = Result<T, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut inner = self.inner.lock().unwrap();
+        match &inner.state {
+            PromiseState::Fulfilled(value) => Poll::Ready(Ok(value.clone())),
+            PromiseState::Rejected(error) => Poll::Ready(Err(error.clone())),
+            PromiseState::Pending => {
+                if !inner.wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+                    inner.wakers.push(cx.waker().clone());
+                }
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T, E> Promise<T, E>
+where
+    T: Clone + Send + 'static,
+    E: Clone + Send + 'static,
+{
+    /// Создает Promise из Future. Future опрашивается исполнителем,
+    /// к которому относится spawner, и завершает Promise своим результатом.
+    pub fn from_future<F>(spawner: &Spawner, future: F) -> Self
+    where
+        F: Future<Output = Result<T, E>> + Send + 'static,
+    {
+        let (promise, resolver) = Promise::pending_on(spawner.clone());
+        let task = Arc::new(FutureTask {
+            future: Mutex::new(Some(Box::pin(async move {
+                match future.await {
+                    Ok(value) => resolver.resolve(value),
+                    Err(error) => resolver.reject(error),
+                }
+            }))),
+            spawner: spawner.clone(),
+        });
+        spawner.spawn(move || task.poll());
+        promise
+    }
+}
+
+/// Задача исполнителя, опрашивающая Future. Пробуждение ставит повторный опрос в очередь.
+struct FutureTask {
+    future: Mutex<Option<BoxFuture>>,
+    spawner: Spawner,
+}
+
+impl FutureTask {
+    fn poll(self: Arc<Self>) {
+        let mut slot = self.future.lock().unwrap();
+        if let Some(mut future) = slot.take() {
+            let waker = Waker::from(Arc::clone(&self));
+            let mut cx = Context::from_waker(&waker);
+            if future.as_mut().poll(&mut cx).is_pending() {
+                *slot = Some(future);
+            }
+        }
+    }
+}
+
+impl Wake for FutureTask {
+    fn wake(self: Arc<Self>) {
+        let spawner = self.spawner.clone();
+        spawner.spawn(move || self.poll());
+    }
+}
+
+/// Пробуждение через распарковку ожидающего потока.
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Блокирует текущий поток, пока Future не завершится.
+/// Продолжения Promise, привязанного к Executor, по-прежнему требуют, чтобы исполнитель работал.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+#[test]
+fn test_promise_future() {
+    use super::executor::Executor;
+
+    async fn increment(promise: Promise<i32, String>) -> Result<i32, String> {
+        let value = promise.await?;
+        Ok(value + 1)
+    }
+
+    // Promise внутри async fn, разрешаемый из другого потока
+    let (promise, resolver) = Promise::<i32, String>::pending();
+    let worker = thread::spawn(move || resolver.resolve(41));
+    assert_eq!(block_on(increment(promise)), Ok(42));
+    worker.join().unwrap();
+
+    // Ошибка пробрасывается через ?
+    assert_eq!(
+        block_on(increment(Promise::rejected("boom".to_string()))),
+        Err("boom".to_string())
+    );
+
+    // Future превращается в Promise и опрашивается исполнителем
+    let executor = Executor::new();
+    let (left, left_resolver) = Promise::<i32, String>::pending();
+    let (right, right_resolver) = Promise::<i32, String>::pending();
+    let sum = Promise::<i32, String>::from_future(&executor.spawner(), async move {
+        let a = left.await?;
+        let b = right.await?;
+        Ok(a + b)
+    });
+    let doubled = sum.then(|x| x * 2);
+    let worker = thread::spawn(move || {
+        left_resolver.resolve(1);
+        right_resolver.resolve(2);
+    });
+    assert_eq!(executor.block_on(&doubled), Ok(6));
+    worker.join().unwrap();
+}
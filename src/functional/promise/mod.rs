@@ -0,0 +1,514 @@
+// Функциональный паттерн Promise: представляет асинхронную операцию, которая может завершиться успехом или неудачей.
+// Полезен для обработки асинхронных вычислений в функциональном стиле.
+// Promise можно разрешить позже, в том числе из другого потока, через Resolver.
+// Продолжения then/catch, зарегистрированные до завершения, вызываются после него:
+// сразу в разрешающем потоке или, если Promise привязан к Executor, в потоке исполнителя.
+// Пример: цепочка операций с обработкой ошибок.
+
+pub mod cancel;
+pub mod combinators;
+pub mod executor;
+pub mod future;
+pub mod monad;
+pub mod retry;
+pub mod timeout;
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::task::Waker;
+
+use executor::Spawner;
+
+/// Перечисление для состояния Promise.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PromiseState<T, E> {
+    Pending,
+    Fulfilled(T),
+    Rejected(E),
+}
+
+/// Продолжение, вызываемое после завершения Promise.
+type Reaction<T, E> = Box<dyn FnOnce(Result<T, E>) + Send>;
+
+struct Inner<T, E> {
+    state: PromiseState<T, E>,
+    reactions: Vec<Reaction<T, E>>,
+    wakers: Vec<Waker>,
+    spawner: Option<Spawner>,
+}
+
+/// Promise - разделяемое состояние отложенного вычисления.
+/// Клоны Promise указывают на одно и то же состояние.
+pub struct Promise<T, E> {
+    inner: Arc<Mutex<Inner<T, E>>>,
+}
+
+/// Сторона, которая разрешает или отклоняет Promise. Может быть передана в другой поток.
+pub struct Resolver<T, E> {
+    promise: Promise<T, E>,
+}
+
+impl<T, E> Clone for Promise<T, E> {
+    fn clone(&self) -> Self {
+        Promise {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<T, E> Promise<T, E>
+where
+    T: Clone + Send + 'static,
+    E: Clone + Send + 'static,
+{
+    /// Создает новый Promise в состоянии Pending.
+    pub fn new() -> Self {
+        Promise::with_spawner(None)
+    }
+
+    /// Создает ожидающий Promise и Resolver для него.
+    pub fn pending() -> (Self, Resolver<T, E>) {
+        let promise = Promise::new();
+        let resolver = Resolver {
+            promise: promise.clone(),
+        };
+        (promise, resolver)
+    }
+
+    /// Создает ожидающий Promise, продолжения которого выполняет исполнитель.
+    pub fn pending_on(spawner: Spawner) -> (Self, Resolver<T, E>) {
+        let promise = Promise::with_spawner(Some(spawner));
+        let resolver = Resolver {
+            promise: promise.clone(),
+        };
+        (promise, resolver)
+    }
+
+    /// Создает уже разрешенный Promise.
+    pub fn fulfilled(value: T) -> Self {
+        Promise::new().resolve(value)
+    }
+
+    /// Создает уже отклоненный Promise.
+    pub fn rejected(error: E) -> Self {
+        Promise::new().reject(error)
+    }
+
+    fn with_spawner(spawner: Option<Spawner>) -> Self {
+        Promise {
+            inner: Arc::new(Mutex::new(Inner {
+                state: PromiseState::Pending,
+                reactions: Vec::new(),
+                wakers: Vec::new(),
+                spawner,
+            })),
+        }
+    }
+
+    /// Разрешает Promise с значением. Повторное завершение игнорируется.
+    pub fn resolve(self, value: T) -> Self {
+        self.settle(Ok(value));
+        self
+    }
+
+    /// Отклоняет Promise с ошибкой. Повторное завершение игнорируется.
+    pub fn reject(self, error: E) -> Self {
+        self.settle(Err(error));
+        self
+    }
+
+    /// Применяет функцию к успешному значению, возвращая новый Promise.
+    pub fn then<U, F>(self, f: F) -> Promise<U, E>
+    where
+        U: Clone + Send + 'static,
+        F: FnOnce(T) -> U + Send + 'static,
+    {
+        let next = Promise::with_spawner(self.spawner());
+        let target = next.clone();
+        self.subscribe(move |result| {
+            target.settle(result.map(f));
+        });
+        next
+    }
+
+    /// Обрабатывает ошибку, возвращая новый Promise.
+    pub fn catch<F>(self, f: F) -> Promise<T, E>
+    where
+        F: FnOnce(E) -> T + Send + 'static,
+    {
+        let next = Promise::with_spawner(self.spawner());
+        let target = next.clone();
+        self.subscribe(move |result| {
+            target.settle(result.or_else(|error| Ok(f(error))));
+        });
+        next
+    }
+
+    /// Продолжает цепочку функцией, которая сама возвращает Promise.
+    /// Результирующий Promise завершается вместе с возвращенным.
+    pub fn and_then<U, F>(self, f: F) -> Promise<U, E>
+    where
+        U: Clone + Send + 'static,
+        F: FnOnce(T) -> Promise<U, E> + Send + 'static,
+    {
+        let next = Promise::with_spawner(self.spawner());
+        let target = next.clone();
+        self.subscribe(move |result| match result {
+            Ok(value) => f(value).subscribe(move |inner| {
+                target.settle(inner);
+            }),
+            Err(error) => {
+                target.settle(Err(error));
+            }
+        });
+        next
+    }
+
+    /// Восстанавливается после ошибки функцией, возвращающей новый Promise.
+    pub fn or_else<E2, F>(self, f: F) -> Promise<T, E2>
+    where
+        E2: Clone + Send + 'static,
+        F: FnOnce(E) -> Promise<T, E2> + Send + 'static,
+    {
+        let next = Promise::with_spawner(self.spawner());
+        let target = next.clone();
+        self.subscribe(move |result| match result {
+            Ok(value) => {
+                target.settle(Ok(value));
+            }
+            Err(error) => f(error).subscribe(move |inner| {
+                target.settle(inner);
+            }),
+        });
+        next
+    }
+
+    /// Преобразует ошибку, оставляя успешное значение без изменений.
+    pub fn map_err<E2, F>(self, f: F) -> Promise<T, E2>
+    where
+        E2: Clone + Send + 'static,
+        F: FnOnce(E) -> E2 + Send + 'static,
+    {
+        let next = Promise::with_spawner(self.spawner());
+        let target = next.clone();
+        self.subscribe(move |result| {
+            target.settle(result.map_err(f));
+        });
+        next
+    }
+
+    /// Вызывает функцию после завершения независимо от исхода и передает результат дальше.
+    pub fn finally<F>(self, f: F) -> Promise<T, E>
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let next = Promise::with_spawner(self.spawner());
+        let target = next.clone();
+        self.subscribe(move |result| {
+            f();
+            target.settle(result);
+        });
+        next
+    }
+
+    /// Позволяет заглянуть в успешное значение, не изменяя его.
+    pub fn inspect<F>(self, f: F) -> Promise<T, E>
+    where
+        F: FnOnce(&T) + Send + 'static,
+    {
+        let next = Promise::with_spawner(self.spawner());
+        let target = next.clone();
+        self.subscribe(move |result| {
+            if let Ok(value) = &result {
+                f(value);
+            }
+            target.settle(result);
+        });
+        next
+    }
+
+    /// Проверяет, ожидает ли Promise завершения.
+    #[allow(dead_code)]
+    pub fn is_pending(&self) -> bool {
+        matches!(self.inner.lock().unwrap().state, PromiseState::Pending)
+    }
+
+    /// Проверяет, разрешен ли Promise.
+    pub fn is_fulfilled(&self) -> bool {
+        matches!(self.inner.lock().unwrap().state, PromiseState::Fulfilled(_))
+    }
+
+    /// Проверяет, отклонен ли Promise.
+    #[allow(dead_code)]
+    pub fn is_rejected(&self) -> bool {
+        matches!(self.inner.lock().unwrap().state, PromiseState::Rejected(_))
+    }
+
+    /// Возвращает копию текущего состояния.
+    pub fn state(&self) -> PromiseState<T, E> {
+        self.inner.lock().unwrap().state.clone()
+    }
+
+    /// Возвращает результат, если Promise уже завершен.
+    pub fn result(&self) -> Option<Result<T, E>> {
+        match self.state() {
+            PromiseState::Pending => None,
+            PromiseState::Fulfilled(value) => Some(Ok(value)),
+            PromiseState::Rejected(error) => Some(Err(error)),
+        }
+    }
+
+    /// Регистрирует продолжение. Если Promise уже завершен, продолжение запускается сразу
+    /// (или ставится в очередь исполнителя).
+    pub(crate) fn subscribe<F>(&self, reaction: F)
+    where
+        F: FnOnce(Result<T, E>) + Send + 'static,
+    {
+        let mut inner = self.inner.lock().unwrap();
+        let result = match &inner.state {
+            PromiseState::Pending => {
+                inner.reactions.push(Box::new(reaction));
+                return;
+            }
+            PromiseState::Fulfilled(value) => Ok(value.clone()),
+            PromiseState::Rejected(error) => Err(error.clone()),
+        };
+        let spawner = inner.spawner.clone();
+        drop(inner);
+        dispatch(spawner, Box::new(reaction), result);
+    }
+
+    /// Завершает Promise и запускает накопленные продолжения.
+    /// Возвращает false, если Promise уже был завершен.
+    pub(crate) fn settle(&self, result: Result<T, E>) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        if !matches!(inner.state, PromiseState::Pending) {
+            return false;
+        }
+        inner.state = match &result {
+            Ok(value) => PromiseState::Fulfilled(value.clone()),
+            Err(error) => PromiseState::Rejected(error.clone()),
+        };
+        let reactions = std::mem::take(&mut inner.reactions);
+        let wakers = std::mem::take(&mut inner.wakers);
+        let spawner = inner.spawner.clone();
+        drop(inner);
+
+        for reaction in reactions {
+            dispatch(spawner.clone(), reaction, result.clone());
+        }
+        for waker in wakers {
+            waker.wake();
+        }
+        true
+    }
+
+    pub(crate) fn spawner(&self) -> Option<Spawner> {
+        self.inner.lock().unwrap().spawner.clone()
+    }
+}
+
+/// Запускает продолжение сразу или через исполнитель.
+fn dispatch<T, E>(spawner: Option<Spawner>, reaction: Reaction<T, E>, result: Result<T, E>)
+where
+    T: Send + 'static,
+    E: Send + 'static,
+{
+    match spawner {
+        Some(spawner) => spawner.spawn(move || reaction(result)),
+        None => reaction(result),
+    }
+}
+
+impl<T, E> Default for Promise<T, E>
+where
+    T: Clone + Send + 'static,
+    E: Clone + Send + 'static,
+{
+    fn default() -> Self {
+        Promise::new()
+    }
+}
+
+impl<T, E> Resolver<T, E>
+where
+    T: Clone + Send + 'static,
+    E: Clone + Send + 'static,
+{
+    /// Разрешает связанный Promise.
+    pub fn resolve(self, value: T) {
+        self.promise.settle(Ok(value));
+    }
+
+    /// Отклоняет связанный Promise.
+    pub fn reject(self, error: E) {
+        self.promise.settle(Err(error));
+    }
+}
+
+impl<T: fmt::Debug, E: fmt::Debug> fmt::Debug for Promise<T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Promise")
+            .field(&self.inner.lock().unwrap().state)
+            .finish()
+    }
+}
+
+impl<T: fmt::Display, E: fmt::Display> fmt::Display for Promise<T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.inner.lock().unwrap().state {
+            PromiseState::Pending => write!(f, "Promise: Pending"),
+            PromiseState::Fulfilled(value) => write!(f, "Promise: Fulfilled({})", value),
+            PromiseState::Rejected(error) => write!(f, "Promise: Rejected({})", error),
+        }
+    }
+}
+
+#[test]
+fn test_promise() {
+    // Создание и разрешение Promise
+    let promise = Promise::<i32, &str>::new().resolve(42).then(|x| x * 2);
+
+    assert!(promise.is_fulfilled());
+    println!("{}", promise);
+
+    // Цепочка then
+    let chained = Promise::<i32, &str>::new()
+        .resolve(10)
+        .then(|x| x + 5)
+        .then(|x| x * 3);
+
+    match chained.state() {
+        PromiseState::Fulfilled(value) => assert_eq!(value, 45),
+        _ => panic!("Expected fulfilled"),
+    }
+
+    // Обработка ошибки
+    let rejected = Promise::<i32, &str>::new()
+        .reject("Error occurred")
+        .catch(|_| 0);
+
+    assert!(rejected.is_fulfilled());
+    match rejected.state() {
+        PromiseState::Fulfilled(value) => assert_eq!(value, 0),
+        _ => panic!("Expected fulfilled after catch"),
+    }
+
+    // Продолжение, зарегистрированное до разрешения, срабатывает после него
+    let (pending, resolver) = Promise::<i32, &str>::pending();
+    let doubled = pending.then(|x| x * 2);
+    assert_eq!(doubled.state(), PromiseState::Pending);
+    resolver.resolve(21);
+    assert_eq!(doubled.result(), Some(Ok(42)));
+}
+
+#[test]
+fn test_promise_chaining() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn parse(input: &'static str) -> Promise<i32, String> {
+        match input.parse::<i32>() {
+            Ok(value) => Promise::fulfilled(value),
+            Err(_) => Promise::rejected(format!("not a number: {}", input)),
+        }
+    }
+
+    fn reciprocal(value: i32) -> Promise<f64, String> {
+        if value == 0 {
+            Promise::rejected("division by zero".to_string())
+        } else {
+            Promise::fulfilled(1.0 / value as f64)
+        }
+    }
+
+    // and_then: многошаговая цепочка без вложенных match
+    let result = parse("4").and_then(reciprocal);
+    assert_eq!(result.result(), Some(Ok(0.25)));
+
+    let result = parse("0").and_then(reciprocal);
+    assert_eq!(result.result(), Some(Err("division by zero".to_string())));
+
+    // Ошибка первого шага пропускает последующие
+    let result = parse("x").and_then(reciprocal);
+    assert_eq!(result.result(), Some(Err("not a number: x".to_string())));
+
+    // and_then ждет Promise, возвращенный продолжением
+    let (inner, inner_resolver) = Promise::<i32, String>::pending();
+    let result = parse("1").and_then(move |_| inner);
+    assert!(result.is_pending());
+    inner_resolver.resolve(5);
+    assert_eq!(result.result(), Some(Ok(5)));
+
+    // or_else и map_err
+    let recovered = parse("x").or_else(|_| Promise::<i32, usize>::fulfilled(0));
+    assert_eq!(recovered.result(), Some(Ok(0)));
+
+    let mapped = parse("x").map_err(|error| error.len());
+    assert_eq!(mapped.result(), Some(Err(15)));
+
+    // finally вызывается при любом исходе, inspect - только при успехе
+    let finished = Arc::new(AtomicUsize::new(0));
+    let inspected = Arc::new(AtomicUsize::new(0));
+    for input in ["7", "x"] {
+        let finished = Arc::clone(&finished);
+        let inspected = Arc::clone(&inspected);
+        parse(input)
+            .inspect(move |value| {
+                inspected.fetch_add(*value as usize, Ordering::SeqCst);
+            })
+            .finally(move || {
+                finished.fetch_add(1, Ordering::SeqCst);
+            });
+    }
+    assert_eq!(finished.load(Ordering::SeqCst), 2);
+    assert_eq!(inspected.load(Ordering::SeqCst), 7);
+
+    // Для ожидающего Promise цепочка срабатывает после разрешения
+    let (pending, resolver) = Promise::<&'static str, String>::pending();
+    let result = pending
+        .and_then(parse)
+        .and_then(reciprocal)
+        .map_err(|error| error.len());
+    assert!(result.is_pending());
+    resolver.resolve("2");
+    assert_eq!(result.result(), Some(Ok(0.5)));
+}
+
+#[test]
+fn test_promise_executor() {
+    use executor::Executor;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+
+    let executor = Executor::new();
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    // Продолжения не выполняются, пока исполнитель не разберет очередь
+    let (promise, resolver) = executor.promise::<i32, String>();
+    let counter = Arc::clone(&calls);
+    let chained = promise.then(move |x| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        x + 1
+    });
+    resolver.resolve(1);
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+    assert!(chained.result().is_none());
+
+    assert_eq!(executor.run_until_stalled(), 1);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert_eq!(chained.result(), Some(Ok(2)));
+
+    // Разрешение из другого потока
+    let (promise, resolver) = executor.promise::<i32, String>();
+    let result = promise.then(|x| x * 10).catch(|_| -1);
+    let worker = thread::spawn(move || resolver.resolve(4));
+    assert_eq!(executor.block_on(&result), Ok(40));
+    worker.join().unwrap();
+
+    // Отклонение из другого потока
+    let (promise, resolver) = executor.promise::<i32, String>();
+    let result = promise.then(|x| x * 10);
+    let worker = thread::spawn(move || resolver.reject("boom".to_string()));
+    assert_eq!(executor.block_on(&result), Err("boom".to_string()));
+    worker.join().unwrap();
+}
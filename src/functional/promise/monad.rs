@@ -0,0 +1,117 @@
+// Promise как монада: then - это map, and_then - это bind.
+// Promise хранит продолжение до своего завершения и может вызвать его в другом потоке,
+// поэтому реализует SendMonad, а не Monad: продолжение должно быть Send + 'static.
+// Ждать результата внутри bind не нужно - ни bind, ни zip, ни sequence не блокируют поток.
+
+use super::Promise;
+use crate::functional::lift::Sequence;
+use crate::functional::monad::SendMonad;
+
+/// Для проверки законов в тестах: Promise равны, если оба завершены одинаково.
+/// Ожидающие Promise не равны ничему, кроме самих себя, - их результат еще неизвестен.
+#[cfg(test)]
+impl<T, E> PartialEq for Promise<T, E>
+where
+    T: Clone + Send + PartialEq + 'static,
+    E: Clone + Send + PartialEq + 'static,
+{
+    fn eq(&self, other: &Self) -> bool {
+        if std::sync::Arc::ptr_eq(&self.inner, &other.inner) {
+            return true;
+        }
+        // Состояния читаются по очереди: две блокировки сразу могут привести к взаимной блокировке.
+        match (self.result(), other.result()) {
+            (Some(left), Some(right)) => left == right,
+            _ => false,
+        }
+    }
+}
+
+/// bind - это and_then: результат завершается вместе с Promise, который вернула функция.
+impl<T, E> SendMonad<T> for Promise<T, E>
+where
+    T: Clone + Send + 'static,
+    E: Clone + Send + 'static,
+{
+    type Output<U> = Promise<U, E>;
+
+    fn pure(value: T) -> Self {
+        Promise::fulfilled(value)
+    }
+
+    fn bind<U, F>(self, f: F) -> Promise<U, E>
+    where
+        U: Clone + Send + 'static,
+        F: Fn(T) -> Promise<U, E> + Send + 'static,
+    {
+        self.and_then(f)
+    }
+}
+
+impl<T, E> Promise<T, E>
+where
+    T: Clone + Send + 'static,
+    E: Clone + Send + 'static,
+{
+    /// Пара значений двух Promise; разрешается, когда разрешатся оба.
+    pub fn zip<U: Clone + Send + 'static>(self, other: Promise<U, E>) -> Promise<(T, U), E> {
+        self.and_then(move |left| other.then(move |right| (left, right)))
+    }
+}
+
+/// Результат разрешается через Promise::all.
+impl<T, E> Sequence for Vec<Promise<T, E>>
+where
+    T: Clone + Send + 'static,
+    E: Clone + Send + 'static,
+{
+    type Output = Promise<Vec<T>, E>;
+
+    fn sequence(self) -> Promise<Vec<T>, E> {
+        Promise::all(self)
+    }
+}
+
+#[test]
+fn test_promise_monad() {
+    // Законы монады
+    let f = |x: i32| Promise::<i32, String>::fulfilled(x * 2);
+    let g = |x: i32| {
+        if x > 10 {
+            Promise::rejected(format!("{} is too big", x))
+        } else {
+            Promise::fulfilled(x + 1)
+        }
+    };
+    for m in [
+        Promise::fulfilled(3),
+        Promise::fulfilled(8),
+        Promise::rejected("no".to_string()),
+    ] {
+        assert_eq!(Promise::pure(4).bind(f), f(4));
+        assert_eq!(m.clone().bind(Promise::pure), m);
+        assert_eq!(m.clone().bind(f).bind(g), m.flat_map(move |x| f(x).bind(g)));
+    }
+
+    // Продолжения не блокируют: результат остается в ожидании
+    let (pending, resolver) = Promise::<i32, String>::pending();
+    let doubled = pending.clone().then(|x| x * 2);
+    let total = pending.and_then(|x| Promise::fulfilled(x + 1).then(move |y| x + y));
+    assert!(doubled.is_pending() && total.is_pending());
+    assert_ne!(doubled, total);
+    assert_eq!(doubled, doubled.clone());
+    resolver.resolve(20);
+    assert_eq!(doubled.result(), Some(Ok(40)));
+    assert_eq!(total.result(), Some(Ok(41)));
+
+    // zip и sequence
+    let (pending, resolver) = Promise::<i32, String>::pending();
+    let pair = Promise::fulfilled("one").zip(pending.clone());
+    let all = vec![Promise::fulfilled(1), pending].sequence();
+    assert!(pair.is_pending() && all.is_pending());
+    resolver.resolve(2);
+    assert_eq!(pair.result(), Some(Ok(("one", 2))));
+    assert_eq!(all.result(), Some(Ok(vec![1, 2])));
+    let failed = Promise::<i32, String>::rejected("no".to_string()).zip(Promise::fulfilled(1));
+    assert_eq!(failed.result(), Some(Err("no".to_string())));
+}
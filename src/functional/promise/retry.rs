@@ -0,0 +1,347 @@
+// Повторные попытки для Promise.
+// retry заново вызывает фабрику Promise после отклонения, выдерживая паузу по политике Backoff:
+// фиксированной, экспоненциальной или экспоненциальной со случайным разбросом (jitter).
+// Разброс берется из генератора с заданным seed, а паузы отсчитываются по Clock,
+// поэтому последовательность попыток полностью воспроизводима в тестах.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use super::cancel::{CancellationToken, Cancelled};
+use super::Promise;
+use crate::functional::clock::Clock;
+use crate::functional::random::SeededRng;
+
+/// Политика пауз между попытками.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Backoff {
+    /// Одинаковая пауза перед каждой попыткой.
+    Fixed(Duration),
+    /// Пауза растет в factor раз после каждой попытки, но не выше max.
+    Exponential {
+        initial: Duration,
+        factor: u32,
+        max: Duration,
+    },
+    /// Экспоненциальный рост с "полным" разбросом: пауза выбирается случайно
+    /// от нуля до экспоненциального значения.
+    Jittered {
+        initial: Duration,
+        factor: u32,
+        max: Duration,
+        seed: u64,
+    },
+}
+
+impl Backoff {
+    /// Бесконечная последовательность пауз: перед второй попыткой, третьей и т.д.
+    pub fn delays(&self) -> Delays {
+        let seed = match self {
+            Backoff::Jittered { seed, .. } => *seed,
+            _ => 0,
+        };
+        Delays {
+            backoff: self.clone(),
+            retry: 0,
+            rng: SeededRng::new(seed),
+        }
+    }
+}
+
+/// Итератор пауз политики Backoff.
+pub struct Delays {
+    backoff: Backoff,
+    retry: u32,
+    rng: SeededRng,
+}
+
+impl Iterator for Delays {
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Duration> {
+        let exponential = |initial: Duration, factor: u32, max: Duration, retry: u32| {
+            let multiplier = factor.checked_pow(retry).unwrap_or(u32::MAX);
+            initial.checked_mul(multiplier).unwrap_or(max).min(max)
+        };
+        let delay = match &self.backoff {
+            Backoff::Fixed(delay) => *delay,
+            Backoff::Exponential {
+                initial,
+                factor,
+                max,
+            } => exponential(*initial, *factor, *max, self.retry),
+            Backoff::Jittered {
+                initial,
+                factor,
+                max,
+                ..
+            } => {
+                let cap = exponential(*initial, *factor, *max, self.retry);
+                let nanos = u64::try_from(cap.as_nanos()).unwrap_or(u64::MAX);
+                Duration::from_nanos(self.rng.up_to(nanos))
+            }
+        };
+        self.retry = self.retry.saturating_add(1);
+        Some(delay)
+    }
+}
+
+/// Политика повторных попыток.
+#[derive(Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    backoff: Backoff,
+    token: Option<CancellationToken>,
+}
+
+impl RetryPolicy {
+    /// max_attempts - общее число попыток, включая первую.
+    pub fn new(max_attempts: u32, backoff: Backoff) -> Self {
+        RetryPolicy {
+            max_attempts: max_attempts.max(1),
+            backoff,
+            token: None,
+        }
+    }
+
+    /// Прекращать попытки после отмены токена: результат сразу отклоняется ошибкой Cancelled.
+    pub fn cancel_on(mut self, token: &CancellationToken) -> Self {
+        self.token = Some(token.clone());
+        self
+    }
+}
+
+struct RetryState<F> {
+    factory: F,
+    attempts_left: u32,
+    delays: Delays,
+    token: Option<CancellationToken>,
+    clock: Arc<dyn Clock>,
+}
+
+impl<F> RetryState<F> {
+    fn is_cancelled(&self) -> bool {
+        self.token
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+    }
+}
+
+/// Вызывает factory, пока созданный Promise не будет разрешен или не закончатся попытки.
+/// Результат отклоняется ошибкой последней попытки, а при отмене токена - сразу ошибкой Cancelled.
+/// Продолжения результата выполняет исполнитель первой попытки, как в Promise::all.
+pub fn retry<T, E, F>(factory: F, policy: RetryPolicy, clock: Arc<dyn Clock>) -> Promise<T, E>
+where
+    T: Clone + Send + 'static,
+    E: Clone + Send + From<Cancelled> + 'static,
+    F: FnMut() -> Promise<T, E> + Send + 'static,
+{
+    let state = Arc::new(Mutex::new(RetryState {
+        factory,
+        attempts_left: policy.max_attempts,
+        delays: policy.backoff.delays(),
+        token: policy.token.clone(),
+        clock,
+    }));
+    let first = next_attempt(&state);
+    let output = Promise::with_spawner(first.spawner());
+    if let Some(token) = &policy.token {
+        let target = output.clone();
+        token.on_cancel(move || {
+            target.settle(Err(Cancelled.into()));
+        });
+    }
+    follow(state, first, output.clone());
+    output
+}
+
+fn next_attempt<T, E, F>(state: &Mutex<RetryState<F>>) -> Promise<T, E>
+where
+    F: FnMut() -> Promise<T, E>,
+{
+    let mut guard = state.lock().unwrap();
+    guard.attempts_left -= 1;
+    (guard.factory)()
+}
+
+/// Ждет завершения попытки и по ее результату завершает output или планирует следующую.
+fn follow<T, E, F>(state: Arc<Mutex<RetryState<F>>>, promise: Promise<T, E>, output: Promise<T, E>)
+where
+    T: Clone + Send + 'static,
+    E: Clone + Send + 'static,
+    F: FnMut() -> Promise<T, E> + Send + 'static,
+{
+    promise.subscribe(move |result| {
+        let error = match result {
+            Ok(value) => {
+                output.settle(Ok(value));
+                return;
+            }
+            Err(error) => error,
+        };
+
+        let mut guard = state.lock().unwrap();
+        // После отмены output уже отклонен, новых попыток не будет.
+        if guard.is_cancelled() {
+            return;
+        }
+        if guard.attempts_left == 0 {
+            drop(guard);
+            output.settle(Err(error));
+            return;
+        }
+        let delay = guard.delays.next().unwrap_or_default();
+        let clock = Arc::clone(&guard.clock);
+        drop(guard);
+
+        let deadline = clock.now() + delay;
+        clock.call_at(
+            deadline,
+            Box::new(move || {
+                // Токен могли отменить, пока попытка ждала своей очереди.
+                if state.lock().unwrap().is_cancelled() {
+                    return;
+                }
+                let promise = next_attempt(&state);
+                follow(state, promise, output);
+            }),
+        );
+    });
+}
+
+#[test]
+fn test_promise_retry() {
+    use super::executor::Executor;
+    use crate::functional::clock::ManualClock;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum FetchError {
+        Failed(u32),
+        Cancelled,
+    }
+
+    impl From<Cancelled> for FetchError {
+        fn from(_: Cancelled) -> Self {
+            FetchError::Cancelled
+        }
+    }
+
+    let ms = Duration::from_millis;
+
+    // Последовательности пауз
+    let fixed: Vec<_> = Backoff::Fixed(ms(100)).delays().take(3).collect();
+    assert_eq!(fixed, vec![ms(100), ms(100), ms(100)]);
+
+    let exponential = Backoff::Exponential {
+        initial: ms(10),
+        factor: 2,
+        max: ms(50),
+    };
+    let delays: Vec<_> = exponential.delays().take(5).collect();
+    assert_eq!(delays, vec![ms(10), ms(20), ms(40), ms(50), ms(50)]);
+
+    // Разброс воспроизводим при одинаковом seed и не превышает экспоненциальной паузы
+    let jittered = Backoff::Jittered {
+        initial: ms(10),
+        factor: 2,
+        max: ms(50),
+        seed: 42,
+    };
+    let first: Vec<_> = jittered.delays().take(5).collect();
+    let second: Vec<_> = jittered.delays().take(5).collect();
+    assert_eq!(first, second);
+    for (jitter, cap) in first.iter().zip(&delays) {
+        assert!(jitter <= cap);
+    }
+
+    // Две неудачи, затем успех: попытки в моменты 0, 100 и 200 мс
+    let clock = Arc::new(ManualClock::new());
+    let calls = Arc::new(AtomicU32::new(0));
+    let counter = Arc::clone(&calls);
+    let result = retry(
+        move || {
+            let call = counter.fetch_add(1, Ordering::SeqCst) + 1;
+            if call < 3 {
+                Promise::<u32, FetchError>::rejected(FetchError::Failed(call))
+            } else {
+                Promise::fulfilled(call)
+            }
+        },
+        RetryPolicy::new(5, Backoff::Fixed(ms(100))),
+        clock.clone(),
+    );
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    clock.advance(ms(99));
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    clock.advance(ms(1));
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    assert!(result.is_pending());
+    clock.advance(ms(100));
+    assert_eq!(result.result(), Some(Ok(3)));
+
+    // Попытки закончились - ошибка последней попытки
+    let clock = Arc::new(ManualClock::new());
+    let calls = Arc::new(AtomicU32::new(0));
+    let counter = Arc::clone(&calls);
+    let result = retry(
+        move || {
+            let call = counter.fetch_add(1, Ordering::SeqCst) + 1;
+            Promise::<u32, FetchError>::rejected(FetchError::Failed(call))
+        },
+        RetryPolicy::new(3, exponential.clone()),
+        clock.clone(),
+    );
+    clock.advance(ms(1000));
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+    assert_eq!(clock.now(), ms(1000));
+    assert_eq!(result.result(), Some(Err(FetchError::Failed(3))));
+
+    // Отмена сразу отклоняет результат и останавливает новые попытки
+    let clock = Arc::new(ManualClock::new());
+    let token = CancellationToken::new();
+    let calls = Arc::new(AtomicU32::new(0));
+    let counter = Arc::clone(&calls);
+    let result = retry(
+        move || {
+            let call = counter.fetch_add(1, Ordering::SeqCst) + 1;
+            Promise::<u32, FetchError>::rejected(FetchError::Failed(call))
+        },
+        RetryPolicy::new(10, Backoff::Fixed(ms(10))).cancel_on(&token),
+        clock.clone(),
+    );
+    clock.advance(ms(10));
+    assert!(result.is_pending());
+    token.cancel();
+    assert_eq!(result.result(), Some(Err(FetchError::Cancelled)));
+    clock.advance(ms(100));
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+    // Продолжения результата выполняет исполнитель попыток
+    let clock = Arc::new(ManualClock::new());
+    let executor = Arc::new(Executor::new());
+    let calls = Arc::new(AtomicU32::new(0));
+    let counter = Arc::clone(&calls);
+    let attempts = Arc::clone(&executor);
+    let result = retry(
+        move || {
+            let call = counter.fetch_add(1, Ordering::SeqCst) + 1;
+            let (promise, resolver) = attempts.promise();
+            if call < 2 {
+                resolver.reject(FetchError::Failed(call));
+            } else {
+                resolver.resolve(call);
+            }
+            promise
+        },
+        RetryPolicy::new(3, Backoff::Fixed(ms(10))),
+        clock.clone(),
+    )
+    .then(|call| call * 10);
+    executor.run_until_stalled();
+    clock.advance(ms(10));
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    assert!(result.is_pending());
+    executor.run_until_stalled();
+    assert_eq!(result.result(), Some(Ok(20)));
+}
@@ -0,0 +1,124 @@
+// Таймауты для Promise.
+// Если Promise не завершился к сроку по часам Clock, результирующий Promise
+// отклоняется типизированной ошибкой TimeoutError.
+
+use std::fmt;
+use std::time::Duration;
+
+use super::Promise;
+use crate::functional::clock::Clock;
+
+/// Ошибка истечения срока ожидания.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeoutError {
+    /// Момент по часам, к которому Promise должен был завершиться.
+    pub deadline: Duration,
+}
+
+impl fmt::Display for TimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "deadline {:?} elapsed", self.deadline)
+    }
+}
+
+impl<T, E> Promise<T, E>
+where
+    T: Clone + Send + 'static,
+    E: Clone + Send + 'static,
+{
+    /// Ограничивает ожидание абсолютным сроком deadline по часам clock.
+    pub fn timeout_at(self, deadline: Duration, clock: &dyn Clock) -> Promise<T, E>
+    where
+        E: From<TimeoutError>,
+    {
+        let next = Promise::with_spawner(self.spawner());
+        let target = next.clone();
+        self.subscribe(move |result| {
+            target.settle(result);
+        });
+        let target = next.clone();
+        clock.call_at(
+            deadline,
+            Box::new(move || {
+                target.settle(Err(TimeoutError { deadline }.into()));
+            }),
+        );
+        next
+    }
+
+    /// Ограничивает ожидание интервалом after, отсчитанным от текущего времени часов.
+    pub fn timeout(self, after: Duration, clock: &dyn Clock) -> Promise<T, E>
+    where
+        E: From<TimeoutError>,
+    {
+        let deadline = clock.now() + after;
+        self.timeout_at(deadline, clock)
+    }
+}
+
+#[test]
+fn test_promise_timeout_and_cancellation() {
+    use super::cancel::{CancellationToken, Cancelled};
+    use super::future::block_on;
+    use crate::functional::clock::{ManualClock, SystemClock};
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum FetchError {
+        Timeout(TimeoutError),
+        Cancelled,
+    }
+
+    impl From<TimeoutError> for FetchError {
+        fn from(error: TimeoutError) -> Self {
+            FetchError::Timeout(error)
+        }
+    }
+
+    impl From<Cancelled> for FetchError {
+        fn from(_: Cancelled) -> Self {
+            FetchError::Cancelled
+        }
+    }
+
+    let clock = ManualClock::new();
+    let ms = Duration::from_millis;
+
+    // Не успел к сроку - TimeoutError
+    let (slow, _resolver) = Promise::<i32, FetchError>::pending();
+    let limited = slow.timeout(ms(50), &clock);
+    clock.advance(ms(49));
+    assert!(limited.is_pending());
+    clock.advance(ms(1));
+    assert_eq!(
+        limited.result(),
+        Some(Err(FetchError::Timeout(TimeoutError { deadline: ms(50) })))
+    );
+
+    // Успел до срока - поздний таймер ничего не меняет
+    let (fast, resolver) = Promise::<i32, FetchError>::pending();
+    let limited = fast.timeout_at(ms(100), &clock);
+    resolver.resolve(1);
+    clock.advance(ms(100));
+    assert_eq!(limited.result(), Some(Ok(1)));
+
+    // Таймаут на реальных часах
+    let (never, _resolver) = Promise::<i32, FetchError>::pending();
+    let limited = never.timeout(ms(5), &SystemClock::new());
+    assert!(matches!(block_on(limited), Err(FetchError::Timeout(_))));
+
+    // Отмена
+    let token = CancellationToken::new();
+    let (pending, _resolver) = Promise::<i32, FetchError>::pending();
+    let cancellable = pending.with_cancellation(&token);
+    assert!(cancellable.is_pending());
+    token.cancel();
+    assert!(token.is_cancelled());
+    assert_eq!(cancellable.result(), Some(Err(FetchError::Cancelled)));
+
+    // Уже отмененный токен сразу отклоняет новый Promise
+    let (pending, _resolver) = Promise::<i32, FetchError>::pending();
+    assert_eq!(
+        pending.with_cancellation(&token).result(),
+        Some(Err(FetchError::Cancelled))
+    );
+}
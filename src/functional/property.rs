@@ -0,0 +1,540 @@
+// Проверка свойств на случайных данных (property-based testing) без внешних зависимостей.
+// Свойство проверяется на множестве сгенерированных входов; генератор детерминирован
+// и задается seed, поэтому найденный контрпример воспроизводится. Найденный контрпример
+// уменьшается (shrinking) до минимального, на котором свойство все еще нарушается.
+// check_functor_laws и check_applicative_laws проверяют законы для любого типа,
+// который можно построить из сгенерированного значения.
+
+use std::fmt::{self, Debug};
+
+use super::applicative::Applicative;
+use super::functor::Functor;
+use super::random::SeededRng;
+
+/// Трейт для типов, значения которых можно генерировать и уменьшать.
+pub trait Arbitrary: Clone + Debug {
+    /// Генерирует значение. size ограничивает величину чисел и длину коллекций.
+    fn arbitrary(rng: &mut SeededRng, size: u32) -> Self;
+
+    /// Возвращает более простые варианты значения, от самых простых.
+    fn shrink(&self) -> Vec<Self> {
+        Vec::new()
+    }
+}
+
+macro_rules! impl_arbitrary_for_signed {
+    ($($int:ty),*) => {
+        $(
+            impl Arbitrary for $int {
+                fn arbitrary(rng: &mut SeededRng, size: u32) -> Self {
+                    let magnitude = rng.up_to(u64::from(size)) as $int;
+                    if rng.next_u64().is_multiple_of(2) { magnitude } else { -magnitude }
+                }
+
+                fn shrink(&self) -> Vec<Self> {
+                    shrink_towards_zero(*self as i64)
+                        .into_iter()
+                        .map(|value| value as $int)
+                        .collect()
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_arbitrary_for_unsigned {
+    ($($int:ty),*) => {
+        $(
+            impl Arbitrary for $int {
+                fn arbitrary(rng: &mut SeededRng, size: u32) -> Self {
+                    rng.up_to(u64::from(size)) as $int
+                }
+
+                fn shrink(&self) -> Vec<Self> {
+                    shrink_towards_zero(*self as i64)
+                        .into_iter()
+                        .map(|value| value as $int)
+                        .collect()
+                }
+            }
+        )*
+    };
+}
+
+impl_arbitrary_for_signed!(i8, i16, i32, i64);
+impl_arbitrary_for_unsigned!(u8, u16, u32, u64, usize);
+
+/// Кандидаты для числа: ноль, половина, на единицу ближе к нулю.
+fn shrink_towards_zero(value: i64) -> Vec<i64> {
+    let mut candidates = Vec::new();
+    for candidate in [0, value / 2, value - value.signum()] {
+        if candidate != value && !candidates.contains(&candidate) {
+            candidates.push(candidate);
+        }
+    }
+    candidates
+}
+
+impl Arbitrary for bool {
+    fn arbitrary(rng: &mut SeededRng, _size: u32) -> Self {
+        rng.next_u64().is_multiple_of(2)
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        if *self {
+            vec![false]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+impl Arbitrary for char {
+    fn arbitrary(rng: &mut SeededRng, _size: u32) -> Self {
+        (b'a' + rng.up_to(25) as u8) as char
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        if *self == 'a' {
+            Vec::new()
+        } else {
+            vec!['a']
+        }
+    }
+}
+
+impl Arbitrary for String {
+    fn arbitrary(rng: &mut SeededRng, size: u32) -> Self {
+        Vec::<char>::arbitrary(rng, size).into_iter().collect()
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        let chars: Vec<char> = self.chars().collect();
+        chars
+            .shrink()
+            .into_iter()
+            .map(|chars| chars.into_iter().collect())
+            .collect()
+    }
+}
+
+impl<T: Arbitrary> Arbitrary for Vec<T> {
+    fn arbitrary(rng: &mut SeededRng, size: u32) -> Self {
+        let len = rng.up_to(u64::from(size));
+        (0..len).map(|_| T::arbitrary(rng, size)).collect()
+    }
+
+    /// Сначала пустой вектор и половины, затем удаление по одному элементу,
+    /// затем уменьшение отдельных элементов.
+    fn shrink(&self) -> Vec<Self> {
+        let mut candidates = Vec::new();
+        if self.is_empty() {
+            return candidates;
+        }
+        candidates.push(Vec::new());
+        if self.len() > 2 {
+            candidates.push(self[..self.len() / 2].to_vec());
+            candidates.push(self[self.len() / 2..].to_vec());
+        }
+        for index in 0..self.len() {
+            let mut smaller = self.clone();
+            smaller.remove(index);
+            candidates.push(smaller);
+        }
+        for (index, item) in self.iter().enumerate() {
+            for simpler in item.shrink() {
+                let mut smaller = self.clone();
+                smaller[index] = simpler;
+                candidates.push(smaller);
+            }
+        }
+        candidates
+    }
+}
+
+impl<T: Arbitrary> Arbitrary for Option<T> {
+    fn arbitrary(rng: &mut SeededRng, size: u32) -> Self {
+        // Примерно каждое четвертое значение - None.
+        if rng.up_to(3) == 0 {
+            None
+        } else {
+            Some(T::arbitrary(rng, size))
+        }
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        match self {
+            None => Vec::new(),
+            Some(value) => std::iter::once(None)
+                .chain(value.shrink().into_iter().map(Some))
+                .collect(),
+        }
+    }
+}
+
+impl<T: Arbitrary, E: Arbitrary> Arbitrary for Result<T, E> {
+    fn arbitrary(rng: &mut SeededRng, size: u32) -> Self {
+        if rng.up_to(3) == 0 {
+            Err(E::arbitrary(rng, size))
+        } else {
+            Ok(T::arbitrary(rng, size))
+        }
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        match self {
+            Ok(value) => value.shrink().into_iter().map(Ok).collect(),
+            Err(error) => error.shrink().into_iter().map(Err).collect(),
+        }
+    }
+}
+
+impl<A: Arbitrary, B: Arbitrary> Arbitrary for (A, B) {
+    fn arbitrary(rng: &mut SeededRng, size: u32) -> Self {
+        (A::arbitrary(rng, size), B::arbitrary(rng, size))
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        let (a, b) = self;
+        let mut candidates: Vec<Self> = a.shrink().into_iter().map(|a| (a, b.clone())).collect();
+        candidates.extend(b.shrink().into_iter().map(|b| (a.clone(), b)));
+        candidates
+    }
+}
+
+impl<A: Arbitrary, B: Arbitrary, C: Arbitrary> Arbitrary for (A, B, C) {
+    fn arbitrary(rng: &mut SeededRng, size: u32) -> Self {
+        (
+            A::arbitrary(rng, size),
+            B::arbitrary(rng, size),
+            C::arbitrary(rng, size),
+        )
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        let (a, b, c) = self;
+        let mut candidates: Vec<Self> = a
+            .shrink()
+            .into_iter()
+            .map(|a| (a, b.clone(), c.clone()))
+            .collect();
+        candidates.extend(b.shrink().into_iter().map(|b| (a.clone(), b, c.clone())));
+        candidates.extend(c.shrink().into_iter().map(|c| (a.clone(), b.clone(), c)));
+        candidates
+    }
+}
+
+/// Функция в законах. Указатели на функции копируются, поэтому подходят для любого Applicative.
+pub type Fun = fn(i32) -> i32;
+
+/// Функции, из которых выбираются случайные функции для законов.
+const FUNCTIONS: &[(&str, Fun)] = &[
+    ("x", |x| x),
+    ("0", |_| 0),
+    ("x + 1", |x| x.wrapping_add(1)),
+    ("x * 2", |x| x.wrapping_mul(2)),
+    ("-x", |x| x.wrapping_neg()),
+    ("x / 3", |x| x / 3),
+    ("x % 7", |x| x % 7),
+    ("x * x", |x| x.wrapping_mul(x)),
+];
+
+/// Случайная функция i32 -> i32 с читаемым именем для отчета.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Function {
+    index: usize,
+}
+
+impl Function {
+    pub fn name(&self) -> &'static str {
+        FUNCTIONS[self.index].0
+    }
+
+    pub fn as_fn(&self) -> Fun {
+        FUNCTIONS[self.index].1
+    }
+
+    pub fn call(&self, x: i32) -> i32 {
+        self.as_fn()(x)
+    }
+}
+
+impl Debug for Function {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "|x| {}", self.name())
+    }
+}
+
+impl Arbitrary for Function {
+    fn arbitrary(rng: &mut SeededRng, _size: u32) -> Self {
+        Function {
+            index: rng.up_to(FUNCTIONS.len() as u64 - 1) as usize,
+        }
+    }
+
+    /// Более простой считается функция ближе к началу таблицы.
+    fn shrink(&self) -> Vec<Self> {
+        (0..self.index).map(|index| Function { index }).collect()
+    }
+}
+
+/// Настройки проверки.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    /// Число проверяемых входов.
+    pub cases: u32,
+    pub seed: u64,
+    /// Размер входов растет от 0 до max_size.
+    pub max_size: u32,
+    /// Ограничение на число успешных шагов уменьшения.
+    pub max_shrinks: u32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            cases: 100,
+            seed: 0x5EED,
+            max_size: 100,
+            max_shrinks: 1000,
+        }
+    }
+}
+
+impl Config {
+    pub fn with_seed(seed: u64) -> Self {
+        Config {
+            seed,
+            ..Config::default()
+        }
+    }
+}
+
+/// Нарушение свойства: исходный и уменьшенный контрпримеры.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Failure {
+    /// Название свойства или закона.
+    pub property: String,
+    pub seed: u64,
+    /// Номер входа, на котором свойство нарушилось впервые.
+    pub case: u32,
+    pub original: String,
+    pub shrunk: String,
+    pub shrinks: u32,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "property '{}' failed (seed {}, case {}): {} (shrunk from {} in {} steps)",
+            self.property, self.seed, self.case, self.shrunk, self.original, self.shrinks
+        )
+    }
+}
+
+/// Проверяет свойство на config.cases случайных входах.
+pub fn check<T, P>(config: &Config, property: &str, holds: P) -> Result<(), Failure>
+where
+    T: Arbitrary,
+    P: Fn(&T) -> bool,
+{
+    let mut rng = SeededRng::new(config.seed);
+    for case in 0..config.cases {
+        let size = if config.cases > 1 {
+            config.max_size * case / (config.cases - 1)
+        } else {
+            config.max_size
+        };
+        let input = T::arbitrary(&mut rng, size);
+        if holds(&input) {
+            continue;
+        }
+
+        let (shrunk, shrinks) = shrink_failure(config, &input, &holds);
+        return Err(Failure {
+            property: property.to_string(),
+            seed: config.seed,
+            case,
+            original: format!("{:?}", input),
+            shrunk: format!("{:?}", shrunk),
+            shrinks,
+        });
+    }
+    Ok(())
+}
+
+/// Жадно переходит к первому более простому входу, на котором свойство нарушается.
+fn shrink_failure<T, P>(config: &Config, input: &T, holds: &P) -> (T, u32)
+where
+    T: Arbitrary,
+    P: Fn(&T) -> bool,
+{
+    let mut current = input.clone();
+    let mut shrinks = 0;
+    while shrinks < config.max_shrinks {
+        match current
+            .shrink()
+            .into_iter()
+            .find(|candidate| !holds(candidate))
+        {
+            Some(simpler) => {
+                current = simpler;
+                shrinks += 1;
+            }
+            None => break,
+        }
+    }
+    (current, shrinks)
+}
+
+/// Проверяет законы функтора для типа W, построенного из сгенерированного A:
+/// идентичность (map(id) == id) и композицию (map(f).map(g) == map(g . f)).
+pub fn check_functor_laws<A, W, C>(config: &Config, construct: C) -> Result<(), Failure>
+where
+    A: Arbitrary,
+    W: Functor<'static, i32, Output<i32> = W> + Clone + PartialEq,
+    C: Fn(A) -> W,
+{
+    check(config, "functor identity", |input: &A| {
+        let value = construct(input.clone());
+        value.clone().map(|x| x) == value
+    })?;
+    check(
+        config,
+        "functor composition",
+        |(input, f, g): &(A, Function, Function)| {
+            let value = construct(input.clone());
+            let (f, g) = (f.as_fn(), g.as_fn());
+            value.clone().map(f).map(g) == value.map(move |x| g(f(x)))
+        },
+    )
+}
+
+/// Проверяет законы аппликативного функтора для типа W, построенного из сгенерированного A:
+/// идентичность (apply(pure(id)) == id), гомоморфизм (pure(x).apply(pure(f)) == pure(f(x)))
+/// и согласованность с функтором (apply(pure(f)) == map(f)).
+pub fn check_applicative_laws<A, W, C>(config: &Config, construct: C) -> Result<(), Failure>
+where
+    A: Arbitrary,
+    W: Applicative<'static, i32, Output<i32> = W>
+        + Functor<'static, i32, Output<i32> = W>
+        + Clone
+        + PartialEq,
+    <W as Applicative<'static, i32>>::Output<Fun>:
+        Applicative<'static, Fun, Output<Fun> = <W as Applicative<'static, i32>>::Output<Fun>>,
+    C: Fn(A) -> W,
+{
+    let pure_fn = |f: Fun| {
+        <<W as Applicative<'static, i32>>::Output<Fun> as Applicative<'static, Fun>>::pure(f)
+    };
+
+    check(config, "applicative identity", |input: &A| {
+        let value = construct(input.clone());
+        let identity: Fun = |x| x;
+        value.clone().apply::<i32, Fun>(pure_fn(identity)) == value
+    })?;
+    check(
+        config,
+        "applicative homomorphism",
+        |(x, f): &(i32, Function)| {
+            <W as Applicative<'static, i32>>::pure(*x).apply::<i32, Fun>(pure_fn(f.as_fn()))
+                == <W as Applicative<'static, i32>>::pure(f.call(*x))
+        },
+    )?;
+    check(
+        config,
+        "applicative map consistency",
+        |(input, f): &(A, Function)| {
+            let value = construct(input.clone());
+            value.clone().apply::<i32, Fun>(pure_fn(f.as_fn())) == Functor::map(value, f.as_fn())
+        },
+    )
+}
+
+#[test]
+fn test_property_checking() {
+    use super::applicative::AppResult;
+    use super::functor::Maybe;
+    use super::semigroup::NonEmpty;
+    use super::validation::Validation;
+
+    let config = Config::default();
+
+    // Законы для встроенных реализаций
+    check_functor_laws(&config, |x: Option<i32>| x).unwrap();
+    check_functor_laws(&config, |x: Vec<i32>| x).unwrap();
+    check_applicative_laws(&config, |x: Option<i32>| x).unwrap();
+    check_applicative_laws(&config, |x: Vec<i32>| x).unwrap();
+
+    // Пользовательские типы подключаются через функцию построения
+    let maybe = |x: Option<i32>| match x {
+        Some(value) => Maybe::some(value),
+        None => Maybe::none(),
+    };
+    check_functor_laws(&config, maybe).unwrap();
+    check_applicative_laws(&config, maybe).unwrap();
+
+    let app_result = |x: Result<i32, u8>| match x {
+        Ok(value) => AppResult::ok(value),
+        Err(error) => AppResult::err(error),
+    };
+    check_functor_laws(&config, app_result).unwrap();
+    check_applicative_laws(&config, app_result).unwrap();
+
+    let validation = |x: Result<i32, Vec<String>>| match x {
+        Ok(value) => Validation::<i32, Vec<String>>::valid(value),
+        Err(errors) => Validation::invalid(errors),
+    };
+    check_functor_laws(&config, validation).unwrap();
+    check_applicative_laws(&config, validation).unwrap();
+    check_functor_laws(&config, |x: Result<i32, char>| {
+        Validation::<i32, NonEmpty<char>>::from(x)
+    })
+    .unwrap();
+
+    // Нарушенный закон находится и уменьшается до простого контрпримера
+    #[derive(Debug, Clone, PartialEq)]
+    struct Counted(i32, u32);
+
+    impl<'f> Functor<'f, i32> for Counted {
+        type Output<U> = Counted;
+
+        // Ошибка: map считает вызовы и ломает закон идентичности
+        fn map<U, F>(self, f: F) -> Counted
+        where
+            F: Fn(i32) -> U + 'f,
+        {
+            let _ = f;
+            Counted(self.0, self.1 + 1)
+        }
+    }
+
+    let failure = check_functor_laws(&config, |(x, n): (i32, u32)| Counted(x, n)).unwrap_err();
+    assert_eq!(failure.property, "functor identity");
+    assert_eq!(failure.shrunk, "(0, 0)");
+
+    // Уменьшение: любой вектор длины от трех сводится к трем нулям
+    let failure = check(&config, "short vectors", |v: &Vec<i32>| v.len() < 3).unwrap_err();
+    assert_eq!(failure.shrunk, "[0, 0, 0]");
+    assert!(failure.shrinks > 0);
+    assert!(failure.to_string().contains("short vectors"));
+
+    // Один и тот же seed дает один и тот же контрпример
+    let again = check(&config, "short vectors", |v: &Vec<i32>| v.len() < 3).unwrap_err();
+    assert_eq!(again, failure);
+    let other_seed = Config::with_seed(7);
+    assert!(check(&other_seed, "sorted", |v: &Vec<u8>| v
+        .windows(2)
+        .all(|w| w[0] <= w[1]))
+    .is_err());
+
+    // Выполняющееся свойство
+    check(&config, "reverse twice", |v: &Vec<i32>| {
+        let mut reversed = v.clone();
+        reversed.reverse();
+        reversed.reverse();
+        reversed == *v
+    })
+    .unwrap();
+}
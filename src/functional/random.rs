@@ -0,0 +1,37 @@
+// Детерминированные псевдослучайные числа.
+// Генератор задается seed, поэтому одна и та же последовательность воспроизводится
+// в тестах: на нем построены разброс пауз в retry и генерация данных в property.
+
+/// Простой детерминированный генератор псевдослучайных чисел (xorshift64*).
+#[derive(Debug, Clone)]
+pub struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    pub fn new(seed: u64) -> Self {
+        // Нулевое состояние у xorshift вырождено.
+        SeededRng {
+            state: if seed == 0 {
+                0x9E37_79B9_7F4A_7C15
+            } else {
+                seed
+            },
+        }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Случайное число в диапазоне [0, bound]. Включительно.
+    pub fn up_to(&mut self, bound: u64) -> u64 {
+        match bound.checked_add(1) {
+            Some(range) => self.next_u64() % range,
+            None => self.next_u64(),
+        }
+    }
+}
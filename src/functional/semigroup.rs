@@ -0,0 +1,309 @@
+// Функциональный паттерн Semigroup (полугруппа): тип с ассоциативной операцией объединения.
+// Объединение позволяет накапливать значения, не зная заранее, сколько их будет:
+// строки склеиваются, векторы дописываются друг к другу.
+// Monoid (моноид) - полугруппа с нейтральным элементом: пустая строка, пустой вектор, ноль.
+// Для чисел есть несколько объединений, поэтому они выбираются оберткой: Sum, Product, Max, Min.
+// NonEmpty - вектор, в котором гарантированно есть хотя бы один элемент,
+// например список ошибок, который не бывает пустым.
+
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+/// Трейт для полугруппы. combine должен быть ассоциативным:
+/// a.combine(b).combine(c) == a.combine(b.combine(c)).
+pub trait Semigroup {
+    fn combine(self, other: Self) -> Self;
+}
+
+impl Semigroup for String {
+    fn combine(mut self, other: Self) -> Self {
+        self.push_str(&other);
+        self
+    }
+}
+
+impl<T> Semigroup for Vec<T> {
+    fn combine(mut self, other: Self) -> Self {
+        self.extend(other);
+        self
+    }
+}
+
+/// Трейт для моноида. empty - нейтральный элемент:
+/// empty().combine(a) == a.combine(empty()) == a.
+pub trait Monoid: Semigroup {
+    fn empty() -> Self;
+
+    /// Объединяет все значения, для пустой последовательности возвращает empty.
+    fn concat<I>(values: I) -> Self
+    where
+        I: IntoIterator<Item = Self>,
+        Self: Sized,
+    {
+        values.into_iter().fold(Self::empty(), Self::combine)
+    }
+}
+
+impl Monoid for String {
+    fn empty() -> Self {
+        String::new()
+    }
+}
+
+impl<T> Monoid for Vec<T> {
+    fn empty() -> Self {
+        Vec::new()
+    }
+}
+
+/// Option добавляет нейтральный элемент None к любой полугруппе.
+impl<T: Semigroup> Semigroup for Option<T> {
+    fn combine(self, other: Self) -> Self {
+        match (self, other) {
+            (Some(left), Some(right)) => Some(left.combine(right)),
+            (left, None) => left,
+            (None, right) => right,
+        }
+    }
+}
+
+impl<T: Semigroup> Monoid for Option<T> {
+    fn empty() -> Self {
+        None
+    }
+}
+
+impl<A: Semigroup, B: Semigroup> Semigroup for (A, B) {
+    fn combine(self, other: Self) -> Self {
+        (self.0.combine(other.0), self.1.combine(other.1))
+    }
+}
+
+impl<A: Monoid, B: Monoid> Monoid for (A, B) {
+    fn empty() -> Self {
+        (A::empty(), B::empty())
+    }
+}
+
+/// Объединение словарей: значения с одинаковыми ключами объединяются.
+impl<K: Eq + Hash, V: Semigroup> Semigroup for HashMap<K, V> {
+    fn combine(mut self, other: Self) -> Self {
+        for (key, value) in other {
+            let merged = match self.remove(&key) {
+                Some(existing) => existing.combine(value),
+                None => value,
+            };
+            self.insert(key, merged);
+        }
+        self
+    }
+}
+
+impl<K: Eq + Hash, V: Semigroup> Monoid for HashMap<K, V> {
+    fn empty() -> Self {
+        HashMap::new()
+    }
+}
+
+impl<K: Ord, V: Semigroup> Semigroup for BTreeMap<K, V> {
+    fn combine(mut self, other: Self) -> Self {
+        for (key, value) in other {
+            let merged = match self.remove(&key) {
+                Some(existing) => existing.combine(value),
+                None => value,
+            };
+            self.insert(key, merged);
+        }
+        self
+    }
+}
+
+impl<K: Ord, V: Semigroup> Monoid for BTreeMap<K, V> {
+    fn empty() -> Self {
+        BTreeMap::new()
+    }
+}
+
+/// Числа, объединяемые сложением.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Sum<T>(pub T);
+
+/// Числа, объединяемые умножением.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Product<T>(pub T);
+
+/// Числа, объединяемые выбором большего.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Max<T>(pub T);
+
+/// Числа, объединяемые выбором меньшего.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Min<T>(pub T);
+
+macro_rules! impl_numeric_monoids {
+    ($zero:expr, $one:expr; $($num:ty),*) => {
+        $(
+            impl Semigroup for Sum<$num> {
+                fn combine(self, other: Self) -> Self {
+                    Sum(self.0 + other.0)
+                }
+            }
+
+            impl Monoid for Sum<$num> {
+                fn empty() -> Self {
+                    Sum($zero)
+                }
+            }
+
+            impl Semigroup for Product<$num> {
+                fn combine(self, other: Self) -> Self {
+                    Product(self.0 * other.0)
+                }
+            }
+
+            impl Monoid for Product<$num> {
+                fn empty() -> Self {
+                    Product($one)
+                }
+            }
+
+            impl Semigroup for Max<$num> {
+                fn combine(self, other: Self) -> Self {
+                    if other.0 > self.0 { other } else { self }
+                }
+            }
+
+            impl Monoid for Max<$num> {
+                fn empty() -> Self {
+                    Max(<$num>::MIN)
+                }
+            }
+
+            impl Semigroup for Min<$num> {
+                fn combine(self, other: Self) -> Self {
+                    if other.0 < self.0 { other } else { self }
+                }
+            }
+
+            impl Monoid for Min<$num> {
+                fn empty() -> Self {
+                    Min(<$num>::MAX)
+                }
+            }
+        )*
+    };
+}
+
+impl_numeric_monoids!(0, 1; i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_numeric_monoids!(0.0, 1.0; f32, f64);
+
+/// Непустой вектор: первый элемент хранится отдельно.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NonEmpty<T> {
+    head: T,
+    tail: Vec<T>,
+}
+
+impl<T> NonEmpty<T> {
+    pub fn new(head: T) -> Self {
+        NonEmpty {
+            head,
+            tail: Vec::new(),
+        }
+    }
+
+    /// Возвращает None для пустого вектора.
+    pub fn from_vec(mut values: Vec<T>) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+        let head = values.remove(0);
+        Some(NonEmpty { head, tail: values })
+    }
+
+    pub fn push(&mut self, value: T) {
+        self.tail.push(value);
+    }
+
+    pub fn first(&self) -> &T {
+        &self.head
+    }
+
+    pub fn len(&self) -> usize {
+        self.tail.len() + 1
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        std::iter::once(&self.head).chain(self.tail.iter())
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        let mut values = Vec::with_capacity(self.len());
+        values.push(self.head);
+        values.extend(self.tail);
+        values
+    }
+}
+
+impl<T> Semigroup for NonEmpty<T> {
+    fn combine(mut self, other: Self) -> Self {
+        self.tail.push(other.head);
+        self.tail.extend(other.tail);
+        self
+    }
+}
+
+#[test]
+fn test_semigroup() {
+    // Ассоциативность для строк и векторов
+    let (a, b, c) = ("ab".to_string(), "cd".to_string(), "ef".to_string());
+    assert_eq!(
+        a.clone().combine(b.clone()).combine(c.clone()),
+        a.combine(b.combine(c))
+    );
+    assert_eq!(vec![1].combine(vec![2, 3]).combine(vec![]), vec![1, 2, 3]);
+
+    // NonEmpty
+    let mut errors = NonEmpty::new("first");
+    errors.push("second");
+    let errors = errors.combine(NonEmpty::new("third"));
+    assert_eq!(errors.len(), 3);
+    assert_eq!(*errors.first(), "first");
+    assert_eq!(errors.iter().count(), 3);
+    assert_eq!(errors.into_vec(), vec!["first", "second", "third"]);
+
+    // Моноиды чисел
+    assert_eq!(Sum::concat([1, 2, 3, 4].map(Sum)), Sum(10));
+    assert_eq!(Product::concat([1, 2, 3, 4].map(Product)), Product(24));
+    assert_eq!(Max::concat([3, -1, 7].map(Max)), Max(7));
+    assert_eq!(Min::concat(Vec::<Min<i32>>::new()), Min(i32::MAX));
+    assert_eq!(Sum(1.5).combine(Sum::empty()), Sum(1.5));
+
+    // Строки, Option и пары
+    assert_eq!(String::concat(["a", "b", "c"].map(String::from)), "abc");
+    assert_eq!(
+        Some(Sum(2)).combine(None).combine(Some(Sum(3))),
+        Some(Sum(5))
+    );
+    assert_eq!(Option::<Sum<i32>>::empty(), None);
+    assert_eq!(
+        (Sum(1), "x".to_string()).combine((Sum(2), "y".to_string())),
+        (Sum(3), "xy".to_string())
+    );
+
+    // Словари: значения с общими ключами объединяются
+    let left = HashMap::from([("apples", Sum(2)), ("pears", Sum(1))]);
+    let right = HashMap::from([("apples", Sum(3)), ("plums", Sum(4))]);
+    let merged = left.combine(right);
+    assert_eq!(merged.len(), 3);
+    assert_eq!(merged["apples"], Sum(5));
+    let words =
+        BTreeMap::from([(1, vec!["a"])]).combine(BTreeMap::from([(1, vec!["b"]), (2, vec!["c"])]));
+    assert_eq!(words, BTreeMap::from([(1, vec!["a", "b"]), (2, vec!["c"])]));
+
+    assert_eq!(NonEmpty::<i32>::from_vec(Vec::new()), None);
+    assert_eq!(
+        NonEmpty::from_vec(vec![1, 2]).unwrap().into_vec(),
+        vec![1, 2]
+    );
+}
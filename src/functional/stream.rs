@@ -0,0 +1,383 @@
+// Функциональный паттерн Stream: ленивые, возможно бесконечные потоки.
+// Поток - это цепочка отложенных вычислений (Thunk): элемент вычисляется при первом
+// обращении и запоминается через OnceLock, как в creational::lazy_initialization,
+// поэтому повторный обход не повторяет работу. take, filter, zip и map тоже ленивые:
+// они описывают новый поток, не вычисляя ни одного элемента.
+// Пример: простые числа (решето Эратосфена) и числа Фибоначчи.
+
+use std::cell::Cell;
+use std::fmt;
+use std::rc::Rc;
+use std::sync::OnceLock;
+
+use super::functor::Functor;
+
+type Init<T> = Box<dyn FnOnce() -> T>;
+
+/// Отложенное вычисление: выполняется при первом обращении, результат запоминается.
+pub struct Thunk<T> {
+    value: OnceLock<T>,
+    init: Cell<Option<Init<T>>>,
+}
+
+impl<T> Thunk<T> {
+    pub fn new<F>(init: F) -> Self
+    where
+        F: FnOnce() -> T + 'static,
+    {
+        Thunk {
+            value: OnceLock::new(),
+            init: Cell::new(Some(Box::new(init))),
+        }
+    }
+
+    /// Уже вычисленное значение.
+    pub fn ready(value: T) -> Self {
+        Thunk {
+            value: OnceLock::from(value),
+            init: Cell::new(None),
+        }
+    }
+
+    /// Вычисляет значение при первом вызове и возвращает запомненное.
+    pub fn force(&self) -> &T {
+        self.value.get_or_init(|| {
+            let init = self.init.take().expect("thunk forced recursively");
+            init()
+        })
+    }
+
+    pub fn is_forced(&self) -> bool {
+        self.value.get().is_some()
+    }
+}
+
+enum Step<T> {
+    Empty,
+    Cons(T, Stream<T>),
+}
+
+impl<T: Clone> Clone for Step<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Step::Empty => Step::Empty,
+            Step::Cons(head, tail) => Step::Cons(head.clone(), tail.clone()),
+        }
+    }
+}
+
+/// Ленивый поток значений. Клонирование разделяет уже вычисленные элементы.
+pub struct Stream<T> {
+    step: Rc<Thunk<Step<T>>>,
+}
+
+impl<T> Clone for Stream<T> {
+    fn clone(&self) -> Self {
+        Stream {
+            step: Rc::clone(&self.step),
+        }
+    }
+}
+
+impl<T> Stream<T> {
+    fn defer<F>(step: F) -> Self
+    where
+        F: FnOnce() -> Step<T> + 'static,
+    {
+        Stream {
+            step: Rc::new(Thunk::new(step)),
+        }
+    }
+
+    fn step(&self) -> &Step<T> {
+        self.step.force()
+    }
+
+    pub fn empty() -> Self {
+        Stream {
+            step: Rc::new(Thunk::ready(Step::Empty)),
+        }
+    }
+
+    /// Первый элемент; вычисляет его при необходимости.
+    pub fn head(&self) -> Option<&T> {
+        match self.step() {
+            Step::Cons(head, _) => Some(head),
+            Step::Empty => None,
+        }
+    }
+
+    /// Поток без первого элемента; для пустого потока - None.
+    pub fn tail(&self) -> Option<Stream<T>> {
+        match self.step() {
+            Step::Cons(_, tail) => Some(tail.clone()),
+            Step::Empty => None,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head().is_none()
+    }
+
+    /// Вычислен ли первый элемент.
+    pub fn is_forced(&self) -> bool {
+        self.step.is_forced()
+    }
+}
+
+impl<T: Clone + 'static> Stream<T> {
+    /// Поток с известным первым элементом и отложенным хвостом.
+    pub fn cons<F>(head: T, tail: F) -> Self
+    where
+        F: FnOnce() -> Stream<T> + 'static,
+    {
+        let tail = Stream::defer(move || tail().step().clone());
+        Stream {
+            step: Rc::new(Thunk::ready(Step::Cons(head, tail))),
+        }
+    }
+
+    /// Поток из состояния: f возвращает следующий элемент и новое состояние
+    /// или None, чтобы закончить поток.
+    pub fn unfold<S, F>(seed: S, f: F) -> Self
+    where
+        S: 'static,
+        F: Fn(S) -> Option<(T, S)> + 'static,
+    {
+        fn go<T: 'static, S: 'static>(seed: S, f: Rc<dyn Fn(S) -> Option<(T, S)>>) -> Stream<T> {
+            Stream::defer(move || match f(seed) {
+                Some((value, next)) => Step::Cons(value, go(next, f)),
+                None => Step::Empty,
+            })
+        }
+        go(seed, Rc::new(f))
+    }
+
+    /// Бесконечный поток seed, f(seed), f(f(seed)), ...
+    pub fn iterate<F>(seed: T, f: F) -> Self
+    where
+        F: Fn(&T) -> T + 'static,
+    {
+        Stream::unfold(seed, move |value| {
+            let next = f(&value);
+            Some((value, next))
+        })
+    }
+
+    /// Первые count элементов.
+    pub fn take(&self, count: usize) -> Self {
+        let stream = self.clone();
+        Stream::defer(move || {
+            if count == 0 {
+                return Step::Empty;
+            }
+            match stream.step() {
+                Step::Cons(head, tail) => Step::Cons(head.clone(), tail.take(count - 1)),
+                Step::Empty => Step::Empty,
+            }
+        })
+    }
+
+    /// Элементы, удовлетворяющие условию. Поиск следующего элемента
+    /// не закончится, если в бесконечном потоке таких больше нет.
+    pub fn filter<P>(&self, predicate: P) -> Self
+    where
+        P: Fn(&T) -> bool + 'static,
+    {
+        fn go<T: Clone + 'static>(
+            mut stream: Stream<T>,
+            predicate: Rc<dyn Fn(&T) -> bool>,
+        ) -> Stream<T> {
+            Stream::defer(move || loop {
+                let next = match stream.step() {
+                    Step::Cons(head, tail) if predicate(head) => {
+                        return Step::Cons(head.clone(), go(tail.clone(), predicate));
+                    }
+                    Step::Cons(_, tail) => tail.clone(),
+                    Step::Empty => return Step::Empty,
+                };
+                stream = next;
+            })
+        }
+        go(self.clone(), Rc::new(predicate))
+    }
+
+    /// Пары элементов двух потоков; заканчивается вместе с более коротким.
+    pub fn zip<U: Clone + 'static>(&self, other: &Stream<U>) -> Stream<(T, U)> {
+        let (left, right) = (self.clone(), other.clone());
+        Stream::defer(move || match (left.step(), right.step()) {
+            (Step::Cons(a, left), Step::Cons(b, right)) => {
+                Step::Cons((a.clone(), b.clone()), left.zip(right))
+            }
+            _ => Step::Empty,
+        })
+    }
+
+    /// Итератор по элементам; для бесконечного потока - бесконечный.
+    pub fn iter(&self) -> Iter<T> {
+        Iter { next: self.clone() }
+    }
+
+    /// Вычисляет все элементы конечного потока.
+    pub fn to_vec(&self) -> Vec<T> {
+        self.iter().collect()
+    }
+}
+
+/// Ленивое применение функции к элементам. Поток хранит функцию до вычисления
+/// элементов, поэтому функтором он является только для 'static функций.
+impl<T: Clone + 'static> Functor<'static, T> for Stream<T> {
+    type Output<U> = Stream<U>;
+
+    fn map<U, F>(self, f: F) -> Stream<U>
+    where
+        F: Fn(T) -> U + 'static,
+    {
+        // Хранится сама функция F, а не dyn Fn(T) -> U: так U не обязан быть 'static
+        fn go<T, U, F>(stream: Stream<T>, f: Rc<F>) -> Stream<U>
+        where
+            T: Clone + 'static,
+            F: Fn(T) -> U + 'static,
+        {
+            Stream::defer(move || match stream.step() {
+                Step::Cons(head, tail) => Step::Cons(f(head.clone()), go(tail.clone(), f)),
+                Step::Empty => Step::Empty,
+            })
+        }
+        go(self, Rc::new(f))
+    }
+}
+
+// Вычисленная цепочка освобождается в цикле, а не рекурсивно, как в persistent::list.
+impl<T> Drop for Stream<T> {
+    fn drop(&mut self) {
+        let mut next = self.take_tail();
+        while let Some(mut stream) = next {
+            next = stream.take_tail();
+        }
+    }
+}
+
+impl<T> Stream<T> {
+    /// Забирает хвост из вычисленного шага, если поток ни с кем его не разделяет.
+    fn take_tail(&mut self) -> Option<Stream<T>> {
+        match Rc::get_mut(&mut self.step)?.value.take()? {
+            Step::Cons(_, tail) => Some(tail),
+            Step::Empty => None,
+        }
+    }
+}
+
+/// Итератор по элементам потока.
+pub struct Iter<T> {
+    next: Stream<T>,
+}
+
+impl<T: Clone + 'static> Iterator for Iter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let (head, tail) = match self.next.step() {
+            Step::Cons(head, tail) => (head.clone(), tail.clone()),
+            Step::Empty => return None,
+        };
+        self.next = tail;
+        Some(head)
+    }
+}
+
+/// Собирает конечный поток, все элементы которого уже вычислены.
+impl<T> FromIterator<T> for Stream<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let values: Vec<T> = iter.into_iter().collect();
+        values
+            .into_iter()
+            .rev()
+            .fold(Stream::empty(), |tail, head| Stream {
+                step: Rc::new(Thunk::ready(Step::Cons(head, tail))),
+            })
+    }
+}
+
+/// Показывает только вычисленные элементы, не вычисляя новых.
+impl<T: fmt::Debug> fmt::Debug for Stream<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut list = f.debug_list();
+        let mut stream = self;
+        while let Some(step) = stream.step.value.get() {
+            match step {
+                Step::Cons(head, tail) => {
+                    list.entry(head);
+                    stream = tail;
+                }
+                Step::Empty => return list.finish(),
+            }
+        }
+        list.entry(&format_args!("..")).finish()
+    }
+}
+
+#[test]
+fn test_stream() {
+    use std::cell::Cell;
+
+    // Бесконечный поток чисел Фибоначчи
+    let fibonacci = Stream::unfold((0u64, 1u64), |(a, b)| Some((a, (b, a + b))));
+    assert_eq!(
+        fibonacci.take(10).to_vec(),
+        [0, 1, 1, 2, 3, 5, 8, 13, 21, 34]
+    );
+
+    // Простые числа: решето Эратосфена на фильтрах
+    fn sieve(numbers: Stream<u64>) -> Stream<u64> {
+        let prime = *numbers.head().unwrap();
+        let rest = numbers.tail().unwrap();
+        Stream::cons(prime, move || sieve(rest.filter(move |n| n % prime != 0)))
+    }
+    let primes = sieve(Stream::iterate(2, |n| n + 1));
+    assert_eq!(
+        primes.take(10).to_vec(),
+        [2, 3, 5, 7, 11, 13, 17, 19, 23, 29]
+    );
+
+    // Элементы вычисляются один раз и только по требованию
+    let calls = Rc::new(Cell::new(0));
+    let counter = Rc::clone(&calls);
+    let squares = Stream::iterate(1u32, move |n| {
+        counter.set(counter.get() + 1);
+        n + 1
+    })
+    .map(|n| n * n);
+    assert!(!squares.is_forced());
+    assert_eq!(calls.get(), 0);
+    assert_eq!(squares.take(5).to_vec(), [1, 4, 9, 16, 25]);
+    let after_first = calls.get();
+    assert_eq!(squares.take(5).to_vec(), [1, 4, 9, 16, 25]);
+    assert_eq!(calls.get(), after_first);
+    assert_eq!(format!("{:?}", squares.take(0)), "[..]");
+    assert!(format!("{:?}", squares).starts_with("[1, 4, 9, 16, 25"));
+
+    // Поток - функтор: общий код для Vec и Stream
+    fn halves<C: Functor<'static, u32>>(numbers: C) -> C::Output<u32> {
+        numbers.map(|n| n / 2)
+    }
+    assert_eq!(halves(vec![2, 4]), [1, 2]);
+    assert_eq!(
+        halves(Stream::iterate(2, |n| n + 2)).take(3).to_vec(),
+        [1, 2, 3]
+    );
+
+    // zip и конечные потоки
+    let names: Stream<&str> = ["a", "b", "c"].into_iter().collect();
+    let indexed = names.zip(&Stream::iterate(0usize, |n| n + 1));
+    assert_eq!(indexed.to_vec(), [("a", 0), ("b", 1), ("c", 2)]);
+    let evens = Stream::iterate(0, |n| n + 1).filter(|n| n % 2 == 0);
+    assert_eq!(evens.iter().skip(3).take(3).collect::<Vec<_>>(), [6, 8, 10]);
+    assert!(Stream::<i32>::empty().is_empty());
+    assert_eq!(format!("{:?}", names), "[\"a\", \"b\", \"c\"]");
+
+    // Длинная вычисленная цепочка освобождается без переполнения стека
+    let long = Stream::iterate(0u64, |n| n + 1);
+    assert_eq!(long.iter().nth(200_000), Some(200_000));
+    drop(long);
+}
@@ -0,0 +1,291 @@
+// Функциональный паттерн Validation: аппликативный функтор, накапливающий ошибки.
+// В отличие от AppResult, который останавливается на первой ошибке, Validation
+// проверяет все значения и объединяет ошибки через Semigroup (обычно NonEmpty).
+// Это нужно для проверки форм и конфигураций, где пользователь должен сразу увидеть
+// все ошибки. Монадой Validation не является: bind не может проверить следующий шаг,
+// не получив значение предыдущего.
+// Пример: проверка полей структуры с помощью check и макроса validate!.
+
+use std::fmt;
+
+use super::applicative::Applicative;
+use super::functor::Functor;
+use super::semigroup::{NonEmpty, Semigroup};
+
+/// Результат проверки: значение или накопленные ошибки.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Validation<T, E> {
+    Valid(T),
+    Invalid(E),
+}
+
+impl<T, E> Validation<T, E> {
+    pub fn valid(value: T) -> Self {
+        Validation::Valid(value)
+    }
+
+    pub fn invalid(error: E) -> Self {
+        Validation::Invalid(error)
+    }
+
+    pub fn is_valid(&self) -> bool {
+        matches!(self, Validation::Valid(_))
+    }
+
+    pub fn into_result(self) -> Result<T, E> {
+        match self {
+            Validation::Valid(value) => Ok(value),
+            Validation::Invalid(error) => Err(error),
+        }
+    }
+
+    /// Преобразует ошибки, не меняя значение.
+    pub fn map_err<E2, F>(self, f: F) -> Validation<T, E2>
+    where
+        F: FnOnce(E) -> E2,
+    {
+        match self {
+            Validation::Valid(value) => Validation::Valid(value),
+            Validation::Invalid(error) => Validation::Invalid(f(error)),
+        }
+    }
+}
+
+impl<T, E: Semigroup> Validation<T, E> {
+    /// Объединяет две проверки в пару. Ошибки обеих проверок накапливаются.
+    pub fn zip<U>(self, other: Validation<U, E>) -> Validation<(T, U), E> {
+        match (self, other) {
+            (Validation::Valid(left), Validation::Valid(right)) => Validation::Valid((left, right)),
+            (Validation::Invalid(left), Validation::Invalid(right)) => {
+                Validation::Invalid(left.combine(right))
+            }
+            (Validation::Invalid(error), _) | (_, Validation::Invalid(error)) => {
+                Validation::Invalid(error)
+            }
+        }
+    }
+}
+
+impl<T, E> Validation<T, NonEmpty<E>> {
+    /// Создает проверку с единственной ошибкой.
+    pub fn fail(error: E) -> Self {
+        Validation::Invalid(NonEmpty::new(error))
+    }
+}
+
+impl<T, E> From<Result<T, E>> for Validation<T, NonEmpty<E>> {
+    fn from(result: Result<T, E>) -> Self {
+        match result {
+            Ok(value) => Validation::Valid(value),
+            Err(error) => Validation::fail(error),
+        }
+    }
+}
+
+impl<'f, T, E> Functor<'f, T> for Validation<T, E> {
+    type Output<U> = Validation<U, E>;
+
+    fn map<U, F>(self, f: F) -> Validation<U, E>
+    where
+        F: Fn(T) -> U + 'f,
+    {
+        match self {
+            Validation::Valid(value) => Validation::Valid(f(value)),
+            Validation::Invalid(error) => Validation::Invalid(error),
+        }
+    }
+}
+
+/// Ошибки значения идут перед ошибками функции.
+impl<'f, T, E: Semigroup> Applicative<'f, T> for Validation<T, E> {
+    type Output<U> = Validation<U, E>;
+
+    fn pure(value: T) -> Validation<T, E> {
+        Validation::Valid(value)
+    }
+
+    fn apply<U, F>(self, f: Validation<F, E>) -> Validation<U, E>
+    where
+        F: Fn(T) -> U + 'f,
+    {
+        self.zip(f).map(|(value, func)| func(value))
+    }
+}
+
+/// Ошибка проверки поля структуры.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldError {
+    pub field: &'static str,
+    pub message: String,
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+/// Проверка одного поля: правила применяются все, ошибки накапливаются.
+pub struct FieldCheck<T> {
+    field: &'static str,
+    value: T,
+    errors: Vec<FieldError>,
+}
+
+/// Начинает проверку значения поля.
+pub fn check<T>(field: &'static str, value: T) -> FieldCheck<T> {
+    FieldCheck {
+        field,
+        value,
+        errors: Vec::new(),
+    }
+}
+
+impl<T> FieldCheck<T> {
+    /// Добавляет правило: если predicate ложен, поле получает ошибку message.
+    pub fn rule<P>(mut self, predicate: P, message: impl Into<String>) -> Self
+    where
+        P: FnOnce(&T) -> bool,
+    {
+        if !predicate(&self.value) {
+            self.errors.push(FieldError {
+                field: self.field,
+                message: message.into(),
+            });
+        }
+        self
+    }
+
+    /// Завершает проверку поля.
+    pub fn done(self) -> Validation<T, NonEmpty<FieldError>> {
+        match NonEmpty::from_vec(self.errors) {
+            Some(errors) => Validation::Invalid(errors),
+            None => Validation::Valid(self.value),
+        }
+    }
+}
+
+/// Собирает структуру из проверок ее полей. Если хотя бы одна проверка не прошла,
+/// возвращает ошибки всех полей в порядке перечисления.
+///
+/// ```ignore
+/// let user = validate!(User {
+///     name: check("name", name).rule(|n| !n.is_empty(), "must not be empty").done(),
+///     age: check("age", age).rule(|a| *a >= 18, "must be adult").done(),
+/// });
+/// ```
+#[macro_export]
+macro_rules! validate {
+    ($ty:ident { $($field:ident : $check:expr),+ $(,)? }) => {{
+        use $crate::functional::semigroup::Semigroup;
+        use $crate::functional::validation::Validation;
+
+        let mut errors = None;
+        $(
+            let $field = match $check {
+                Validation::Valid(value) => Some(value),
+                Validation::Invalid(error) => {
+                    errors = Some(match errors.take() {
+                        Some(previous) => Semigroup::combine(previous, error),
+                        None => error,
+                    });
+                    None
+                }
+            };
+        )+
+        match errors {
+            Some(errors) => Validation::Invalid(errors),
+            None => Validation::Valid($ty { $($field: $field.unwrap()),+ }),
+        }
+    }};
+}
+
+#[test]
+fn test_validation() {
+    #[derive(Debug, PartialEq)]
+    struct Signup {
+        login: String,
+        age: u32,
+        email: String,
+    }
+
+    fn signup(login: &str, age: u32, email: &str) -> Validation<Signup, NonEmpty<FieldError>> {
+        validate!(Signup {
+            login: check("login", login.to_string())
+                .rule(|l| l.len() >= 3, "too short")
+                .rule(
+                    |l| l.chars().all(char::is_alphanumeric),
+                    "must be alphanumeric"
+                )
+                .done(),
+            age: check("age", age)
+                .rule(|a| *a >= 18, "must be at least 18")
+                .done(),
+            email: check("email", email.to_string())
+                .rule(|e| e.contains('@'), "must contain @")
+                .done(),
+        })
+    }
+
+    // Все поля корректны
+    assert_eq!(
+        signup("alice", 30, "alice@example.com"),
+        Validation::valid(Signup {
+            login: "alice".to_string(),
+            age: 30,
+            email: "alice@example.com".to_string(),
+        })
+    );
+
+    // Все ошибки сразу, в порядке полей и правил
+    let errors: Vec<String> = signup("a!", 12, "nowhere")
+        .into_result()
+        .unwrap_err()
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(
+        errors,
+        vec![
+            "login: too short",
+            "login: must be alphanumeric",
+            "age: must be at least 18",
+            "email: must contain @",
+        ]
+    );
+
+    // Applicative накапливает ошибки, а AppResult остановился бы на первой
+    let value: Validation<i32, Vec<&str>> = Validation::invalid(vec!["bad value"]);
+    let func: Validation<fn(i32) -> i32, Vec<&str>> = Validation::invalid(vec!["bad function"]);
+    assert_eq!(
+        value.apply(func),
+        Validation::invalid(vec!["bad value", "bad function"])
+    );
+    let value: Validation<i32, Vec<&str>> = Validation::pure(20);
+    assert_eq!(
+        value.apply(Validation::valid(|x: i32| x + 1)),
+        Validation::valid(21)
+    );
+
+    // Пользовательская полугруппа: ошибки склеиваются в одну строку
+    let left: Validation<i32, String> = Validation::invalid("x is negative; ".to_string());
+    let right: Validation<i32, String> = Validation::invalid("y is zero".to_string());
+    assert_eq!(
+        left.zip(right),
+        Validation::invalid("x is negative; y is zero".to_string())
+    );
+
+    // Из Result и обратно
+    let parsed: Validation<i32, NonEmpty<String>> =
+        "42".parse::<i32>().map_err(|e| e.to_string()).into();
+    assert_eq!(parsed.map(|x| x / 2).into_result().ok(), Some(21));
+    assert!(!Validation::<i32, _>::fail("broken").is_valid());
+
+    // Преобразование ошибок: количество вместо списка
+    let counted = signup("a!", 20, "a@b").map_err(|errors| errors.len());
+    assert_eq!(counted, Validation::invalid(2));
+    assert_eq!(
+        Validation::<i32, &str>::valid(1).map_err(str::len),
+        Validation::valid(1)
+    );
+}